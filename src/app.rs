@@ -1,28 +1,61 @@
-//std library
-use std::collections::VecDeque;
-
 //ratatui
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
     /* style::{ Color, Style, Stylize },
     widgets::{Block, Borders, Clear, Paragraph, Wrap, BorderType}, */
     Frame,
     DefaultTerminal,
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseButton, MouseEventKind, self},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseButton, MouseEventKind},
 };
 
+//std
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+
+//rand
+use rand::{ Rng, thread_rng };
+
 //crossterm
-use crossterm::terminal::{enable_raw_mode, disable_raw_mode};
 use crossterm::event::EnableMouseCapture;
 
 //user made ones
-use crate::data::Data;
+use crate::data::{Data, PLAYER, DEALER};
+use crate::cli::Args;
+use crate::components::dealer;
+use crate::components::odds;
+use crate::components::daily::{DailyRecord, DailyRun};
+use crate::components::endless::{EndlessRun, HighScores};
+use crate::components::tutorial::{Tutorial, TUTORIAL_DEALER_HEALTH};
+use crate::components::sandbox::Sandbox;
+use crate::components::debug_commands::DebugCommand;
 use crate::components::enums::ReloadAmount;
-use crate::components::match_data::MatchData;
+use crate::components::loot::LootAnimation;
+use crate::components::items::Items;
+use crate::components::status::StatusKind;
 use crate::ui_components::widget_data::{WidgetData, WidgetKind};
 use crate::event::{AppEvent, Event, EventHandler};
 use crate::ui;
-use crate::ui_components::logger::Logger;
+use crate::ui_components::logger::{LogEntry, Logger};
+use crate::rules::Rules;
+use crate::components::shotgun::ShotgunError;
+use crate::presets::{self, Preset, ShellSource};
+use crate::ui_components::settings::{Settings, SettingsAction};
+use crate::screens::{self, Screen};
+use crate::screens::setup::GameMode;
+use crate::ui_components::console::Console;
+use crate::ui_components::ui_help::Help;
+use crate::ui_components::palette::Palette;
+use crate::ui_components::dialog::{Dialog, Dialogs};
+use crate::ui_components::hit_map::{HitMap, HitTarget};
+use crate::ui_components::windows::{self, Drag};
+use crate::ui_components::ui_help::HelpInput;
+use crate::components::commands;
+use crate::keymap::Keymap;
+use crate::ui_components::inspector::{self, Inspector};
+use crate::ui_components::inventory::Inventory;
+
+/// ticks the dealer waits before taking its turn
+const DEALER_DELAY: usize = 30;
 
 /// Application.
 #[derive(Debug)]
@@ -33,12 +66,62 @@ pub struct App {
     pub events: EventHandler,
     /// game data
     pub data: Data,
-    /// match data
-    pub match_data: MatchData,
     ///holds the information of the widgets
     pub widget_data: WidgetData,
     /// logger will replace log, and it will automatically size to the correct screen size
     pub logger: Logger,
+    /// the current "double or nothing" run, if the player has won at least once
+    pub endless: Option<EndlessRun>,
+    /// local high-score table for endless runs
+    pub high_scores: HighScores,
+    /// today's daily challenge, when started with `--daily`
+    pub daily: Option<DailyRun>,
+    /// the scripted tutorial, when started with `--tutorial`
+    pub tutorial: Option<Tutorial>,
+    /// the hand-set practice table, when started with `--sandbox`
+    pub sandbox: Option<Sandbox>,
+    /// developer console overlay
+    pub console: Console,
+    /// tree view shown in the Data popup
+    pub inspector: Inspector,
+    /// slot cursor of the inventory panel
+    pub inventory: Inventory,
+    /// built-in presets followed by the ones the player saved
+    pub presets: Vec<Preset>,
+    /// the preset being played
+    pub preset: Preset,
+    /// preset picker opened with `o`
+    pub settings: Settings,
+    /// which screen is up, the table is only one of them
+    pub screen: Screen,
+    /// every key binding, read by the input handler and the help overlay
+    pub keymap: Keymap,
+    /// key reference and game glossary opened with `?`
+    pub help: Help,
+    /// command line opened with `:`
+    pub palette: Palette,
+    /// where everything was drawn last frame, for the mouse
+    pub hit_map: RefCell<HitMap>,
+    /// who a click on the shotgun fires at
+    pub aim: usize,
+    /// the window being moved or resized with the mouse
+    pub drag: Option<Drag>,
+    /// reveals secret state in the inspector
+    pub debug: bool,
+    /// the console or debug mode changed or looked into this game, so its result isn't recorded
+    pub unscored: bool,
+    /// what was started last, "play again" starts it over
+    pub mode: GameMode,
+    /// the odds panel is up, if the preset allows it
+    pub show_odds: bool,
+    /// the spent shell tracker is up
+    pub show_tracker: bool,
+    /// dialogs waiting for an answer, they take every key
+    pub dialogs: Dialogs,
+    /// items from the last reload being laid out on the table
+    pub loot: Option<LootAnimation>,
+    /// ticks since the dealer's turn began
+    dealer_timer: usize,
 }
 
 impl Default for App {
@@ -46,28 +129,82 @@ impl Default for App {
         Self {
             running: true,
             events: EventHandler::new(),
-            data: Data::new(),
-            match_data: MatchData::new(),
+            data: Data::default(),
             widget_data: WidgetData::new(),
             logger: Logger::new(),
+            endless: None,
+            high_scores: HighScores::load(),
+            daily: None,
+            tutorial: None,
+            sandbox: None,
+            console: Console::new(),
+            inspector: Inspector::new(),
+            inventory: Inventory::new(),
+            presets: presets::builtin(&Rules::default()),
+            preset: Preset::default(),
+            settings: Settings::default(),
+            screen: Screen::Title { cursor: 0 },
+            keymap: Keymap::new(),
+            help: Help::new(),
+            palette: Palette::new(),
+            hit_map: RefCell::new(HitMap::new()),
+            aim: DEALER,
+            drag: None,
+            debug: false,
+            unscored: false,
+            mode: GameMode::Match,
+            show_odds: true,
+            show_tracker: true,
+            dialogs: Dialogs::new(),
+            loot: None,
+            dealer_timer: 0,
         }
     }
 }
 
 impl App {
     /// Constructs a new instance of [`App`].
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(args: Args, presets: Vec<Preset>, preset: Preset, keymap: Keymap) -> Self {
+        let mut app = Self {
+            keymap,
+            debug: args.debug,
+            data: Data::new(preset.rules.clone()),
+            settings: Settings::new(&preset),
+            presets,
+            preset,
+            ..Self::default()
+        };
+        windows::load(&mut app.widget_data);
+        app.logger.show_debug = args.debug;
+        if !app.preset.log {
+            app.log(LogEntry::system(format!("{} preset: the log is off", app.preset.name)));
+        }
+        //flags that pick a game skip the title menu
+        let mode = if args.tutorial {
+            Some(GameMode::Tutorial)
+        } else if args.sandbox {
+            Some(GameMode::Sandbox)
+        } else if args.daily {
+            Some(GameMode::Daily)
+        } else if args.preset.is_some() {
+            Some(GameMode::Match)
+        } else {
+            None
+        };
+        if let Some(mode) = mode {
+            let preset = app.preset.clone();
+            app.start_game(mode, preset);
+        }
+        app
     }
 
-    pub fn send_log(&mut self, message: Option<String>) {
-        if let Some(msg) = message {
-            let max_size: usize = 1000;
-            if self.logger.log.len() >= max_size {
-                self.logger.log.pop_front();
-            }
-            self.logger.log.push_back(msg)
+    /// keeps `entry` in the log, stamped with the round and, unless it has one, the turn it happened in
+    pub fn log(&mut self, mut entry: LogEntry) {
+        entry.round = self.data.match_data.round_count;
+        if entry.turn.is_none() {
+            entry.turn = self.data.match_data.turn.map(|idx| self.data.players[idx].name().to_string());
         }
+        self.logger.push(entry);
     }
 
     /// Run the application's main loop.
@@ -80,7 +217,11 @@ impl App {
 
                 //changing logger's capacity
                 let area = frame.area();
-                let max_window_lines = ( area.height as f32 / 1.45 ) as usize;
+                //a resized log shows as many lines as fit in it
+                let max_window_lines = match self.widget_data.get_state(WidgetKind::Log).area() {
+                    Some(log_area) => windows::clamp(log_area, area).height.saturating_sub(2) as usize,
+                    None => ( area.height as f32 / 1.45 ) as usize,
+                };
                 self.logger.set_window_size(max_window_lines);
                 self.logger.update_window();
 
@@ -93,96 +234,172 @@ impl App {
                     crossterm::event::Event::Mouse(mouse_event) => self.handle_mouse_events(mouse_event)?,
                     _ => {}
                 },
+                Event::App(app_event) if !self.tutorial_allows(&app_event) => {
+                    if let Some(tutorial) = &self.tutorial {
+                        let msg = format!("Stick to the lesson: {}", tutorial.hint());
+                        self.log(LogEntry::chat(msg));
+                    }
+                },
                 Event::App(app_event) => match app_event {
                     AppEvent::Quit => self.quit(),
-                    AppEvent::Reload(amount) => {
-                        self.data.shotgun.load_random_shells(amount.as_usize());
-                    },
+                    AppEvent::Reload(amount) => self.reload(amount),
                     AppEvent::Shoot => {
-                        if let Some(msg) = self.data.shotgun.shoot() {
-                            self.logger.send_log(Some(msg));
-                            if !self.data.shotgun.is_empty() {
-                                //bring up the confirmation screen
-                                self.widget_data.set_widget(WidgetKind::Confirmation, true, true);
-                            } else {
-                                //bring up the info screen
-
-                            }
+                        if self.data.match_data.turn == Some(PLAYER) {
+                            self.take_shot(PLAYER, DEALER);
+                            self.advance_tutorial();
+                        } else {
+                            self.log(LogEntry::game("It is not your turn").warning());
                         }
                     },
-                    AppEvent::ShowData => {
-                        if self.widget_data.is_displayed(WidgetKind::Data) {
-                            self.widget_data.set_widget(WidgetKind::Data, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Data);
-                            self.widget_data.focus_next();
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
-                            /* if let Some(first) = self.widget_data.render_stack.iter().find(|w| **w != WidgetKind::Shotgun).cloned() {
-                                self.widget_data.kind_focus(&first);
-                            } */
+                    AppEvent::ShootSelf => {
+                        if self.data.match_data.turn == Some(PLAYER) {
+                            self.take_shot(PLAYER, PLAYER);
+                            self.advance_tutorial();
                         } else {
-                            self.widget_data.set_widget(WidgetKind::Data, true, true);
-                            //this is to get the rendering in the right order
-                            self.widget_data.render_stack.push(WidgetKind::Data)
+                            self.log(LogEntry::game("It is not your turn").warning());
                         }
                     },
-
-                    AppEvent::ShowLog => {
-                        if self.widget_data.is_displayed(WidgetKind::Log) {
-                            self.widget_data.set_widget(WidgetKind::Log, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Log);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
+                    AppEvent::UseItem(slot) => {
+                        if self.data.match_data.turn == Some(PLAYER) {
+                            if !self.ask_steal(slot) {
+                                self.use_item(PLAYER, slot);
                             }
                         } else {
-                            self.widget_data.set_widget(WidgetKind::Log, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Log)
+                            self.log(LogEntry::game("It is not your turn").warning());
                         }
                     },
-
-                    AppEvent::ShowInventory => {
-                        if self.widget_data.is_displayed(WidgetKind::Inventory) {
-                            self.widget_data.set_widget(WidgetKind::Inventory, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Inventory);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
+                    AppEvent::Steal { slot, target } => {
+                        if self.data.match_data.turn == Some(PLAYER) {
+                            let result = self.data.use_adrenaline(PLAYER, slot, target);
+                            self.item_used(result);
                         } else {
-                            self.widget_data.set_widget(WidgetKind::Inventory, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Inventory)
+                            self.log(LogEntry::game("It is not your turn").warning());
                         }
                     },
-                    AppEvent::ShowPlayer => {
-                        if self.widget_data.is_displayed(WidgetKind::Player) {
-                            self.widget_data.set_widget(WidgetKind::Player, false, false);
-                            self.widget_data
-                                .render_stack
-                                .retain(|k| *k != WidgetKind::Player);
-                            if let Some(first) = self.widget_data.render_stack.first().cloned() {
-                                self.widget_data.kind_focus(&first);
-                            }
+                    AppEvent::Inventory(input) => self.inventory.handle(input, self.data.rules.inventory_size),
+                    AppEvent::DealerTurn => {
+                        //a dealer with a Magnifying Glass checks before deciding
+                        let dealer = &self.data.players[DEALER];
+                        let glass = dealer.items().iter().position(|item| *item == Items::MagnifyingGlass);
+                        let knows_next = dealer.knowledge().at(&self.data.shotgun, 0).is_some();
+                        if let (Some(slot), false, false) = (glass, knows_next, dealer.has_status(StatusKind::Blind)) {
+                            self.use_item(DEALER, slot);
+                        }
+                        let known_next = self.data.players[DEALER].knowledge().at(&self.data.shotgun, 0).cloned();
+                        let odds = odds::next_shell_odds(&self.data.shotgun, self.data.players[DEALER].knowledge());
+                        let target = if dealer::shoots_self(&mut self.data.rng, &odds) {
+                            DEALER
                         } else {
-                            self.widget_data.set_widget(WidgetKind::Player, true, true);
-                            self.widget_data.render_stack.push(WidgetKind::Player)
+                            PLAYER
+                        };
+                        let knew = known_next.map_or("nothing".to_string(), |shell| format!("{:?}", shell));
+                        self.log(LogEntry::debug(format!("Dealer aims at {} knowing {}", self.data.players[target].name(), knew)));
+                        self.take_shot(DEALER, target);
+                    },
+                    AppEvent::DoubleOrNothing(keep_going) => self.double_or_nothing(keep_going),
+                    AppEvent::TutorialNext => self.advance_tutorial(),
+                    AppEvent::ToggleConsole => self.console.toggle(),
+                    AppEvent::ToggleHelp => self.help.toggle(),
+                    AppEvent::TogglePalette => self.palette.toggle(),
+                    AppEvent::PaletteCommand(line) => match commands::parse(&line, &self) {
+                        Ok(event) => {
+                            self.palette.toggle();
+                            self.events.send(event);
+                        },
+                        Err(err) => self.palette.error = Some(err),
+                    },
+                    AppEvent::SaveLog(_) if !self.preset.log => {
+                        self.log(LogEntry::system(format!("The {} preset plays without the log", self.preset.name)).warning());
+                    },
+                    AppEvent::SaveLog(path) => {
+                        let path = path.unwrap_or_else(|| PathBuf::from(commands::LOG_FILE));
+                        let text: String = self.logger.log.iter().map(|entry| format!("{}\n", entry)).collect();
+                        let entry = match fs::write(&path, text) {
+                            Ok(()) => LogEntry::system(format!("Saved the log to {}", path.display())),
+                            Err(err) => LogEntry::system(format!("Could not save the log to {}: {}", path.display(), err)).error(),
+                        };
+                        self.log(entry);
+                    },
+                    AppEvent::Help(input) => self.help.handle(input),
+                    AppEvent::ConsoleCommand(line) => self.run_debug_command(&line),
+                    AppEvent::ToggleSettings => match self.screen {
+                        Screen::Settings { from_game: true } => self.screen = Screen::Game,
+                        Screen::Settings { from_game: false } => self.screen = Screen::Title { cursor: 0 },
+                        _ if self.daily.is_some() => {
+                            self.log(LogEntry::system("The daily challenge always uses the standard preset").warning());
+                        },
+                        _ => self.screen = Screen::Settings { from_game: true },
+                    },
+                    AppEvent::Settings(input) => match self.settings.handle(input, &self.presets) {
+                        Some(SettingsAction::Play(preset)) => self.apply_preset(preset),
+                        Some(SettingsAction::Name(suggested)) => {
+                            let body = "Name the new preset".to_string();
+                            self.dialogs.push(Dialog::input("Save preset", body, suggested, AppEvent::SavePreset));
+                        },
+                        None => {},
+                    },
+                    AppEvent::SavePreset(name) => match self.settings.save_as(&name, &self.presets) {
+                        Ok(preset) => {
+                            self.log(LogEntry::system(format!("Saved preset {}", preset.name)));
+                            self.presets.push(preset);
+                            if let Err(err) = presets::save_custom(&self.presets) {
+                                self.log(LogEntry::system(format!("Could not save presets: {}", err)).error());
+                            }
+                        },
+                        //ask again with what was typed
+                        Err(msg) => self.dialogs.push(Dialog::input("Save preset", msg, name, AppEvent::SavePreset)),
+                    },
+                    AppEvent::QuitToTitle => {
+                        self.abandon_game();
+                        self.screen = Screen::Title { cursor: 0 };
+                    },
+                    AppEvent::Menu(input) => screens::update(&mut self, input),
+                    AppEvent::Pause => {
+                        if self.screen == Screen::Game {
+                            self.screen = Screen::Paused { cursor: 0 };
+                        }
+                    },
+                    AppEvent::Inspect(input) => {
+                        let tree = inspector::build_tree(&self);
+                        self.inspector.handle(input, &tree);
+                    },
+                    AppEvent::Sandbox(input) => {
+                        let msg = self.sandbox.as_mut().and_then(|sandbox| sandbox.handle(input, &mut self.data));
+                        if let Some(msg) = msg {
+                            self.log(LogEntry::game(msg));
                         }
                     },
+                    AppEvent::Show(WidgetKind::Log) if !self.preset.log => {},
+                    AppEvent::Show(kind) => self.widget_data.toggle_shown(kind),
+                    AppEvent::ShowOdds if !self.preset.odds => {
+                        self.log(LogEntry::system(format!("The {} preset plays without odds", self.preset.name)).warning());
+                    },
+                    AppEvent::ShowOdds => self.show_odds = !self.show_odds,
+                    AppEvent::ShowTracker => self.show_tracker = !self.show_tracker,
                     AppEvent::FocusShotgun => {
                         self.widget_data.toggle_focus(WidgetKind::Shotgun);
                     },
-                    AppEvent::ScrollUp => {
-                        /* if self.logger.log_scroll > 0 {
-                            self.logger.scroll_up();
-                        } */
+                    AppEvent::ScrollUp => self.logger.scroll_up(),
+                    AppEvent::ScrollDown => self.logger.scroll_down(),
+                    AppEvent::Log(input) => self.logger.handle(input),
+                    AppEvent::Focus(kind) => {
+                        self.widget_data.kind_focus(&kind);
+                    },
+                    AppEvent::Window(input) => {
+                        let focused = self.widget_data.shown_widgets().filter(|kind| self.widget_data.pane(*kind).is_some());
+                        let hit_map = self.hit_map.borrow();
+                        let drawn = focused.and_then(|kind| Some((kind, hit_map.area_of(HitTarget::Widget(kind))?)));
+                        let bounds = hit_map.bounds;
+                        drop(hit_map);
+                        if let Some((kind, area)) = drawn {
+                            self.widget_data.set_area(kind, input.apply(area, bounds));
+                            self.save_windows();
+                        }
                     },
-                    AppEvent::ScrollDown => {
-/*                         self.logger.scroll_down(); */
+                    AppEvent::Aim(target) => {
+                        self.aim = target;
+                        let msg = format!("Aiming at {}, click the shotgun to fire", self.data.players[target].name());
+                        self.log(LogEntry::game(msg));
                     },
                     AppEvent::ChangeFocus => {
                         self.widget_data.focus_next();
@@ -190,9 +407,6 @@ impl App {
                     AppEvent::ChangeFocusBack => {
                         self.widget_data.focus_prev();
                     },
-                    _ => {
-                        self.logger.send_log(Some(String::from("Failure to catch event")));
-                    },
                 },
             }
         }
@@ -201,72 +415,674 @@ impl App {
 
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
-        match key_event.code {
-            KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::Quit),
-            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.events.send(AppEvent::Quit)
+        //a dialog takes every key until it is answered
+        if self.dialogs.is_open() {
+            if key_event.code == KeyCode::Char('c') && key_event.modifiers == KeyModifiers::CONTROL {
+                self.events.send(AppEvent::Quit);
+            } else if let Some(event) = self.dialogs.handle(key_event) {
+                self.events.send(event);
             }
-            KeyCode::Char('d' | 'D') => self.events.send(AppEvent::ShowData),
-            KeyCode::Char('l' | 'L') => self.events.send(AppEvent::ShowLog),
-            //KeyCode::Char('i' | 'I') => self.events.send(AppEvent::ShowInventory),
-            KeyCode::Char('p' | 'P') => self.events.send(AppEvent::ShowPlayer),
-            KeyCode::Char('s' | 'S') => self.events.send(AppEvent::FocusShotgun),
-            KeyCode::Char('k') if self.widget_data.is_focused(WidgetKind::Log) => self.events.send(AppEvent::ScrollUp),
-            KeyCode::Char('j') if self.widget_data.is_focused(WidgetKind::Log) => self.events.send(AppEvent::ScrollDown),
-            KeyCode::Tab if key_event.modifiers == KeyModifiers::CONTROL => self.events.send(AppEvent::ChangeFocusBack),
-            KeyCode::Tab => self.events.send(AppEvent::ChangeFocus),
-            KeyCode::Char('r' | 'R') => {
-                match self.match_data.round_count {
-                    1 => self.events.send(AppEvent::Reload(ReloadAmount::One)),
-                    2 => self.events.send(AppEvent::Reload(ReloadAmount::Two)),
-                    3 => self.events.send(AppEvent::Reload(ReloadAmount::Three)),
-                    4 => self.events.send(AppEvent::Reload(ReloadAmount::Four)),
-                    5 => self.events.send(AppEvent::Reload(ReloadAmount::Five)),
-                    _ => self.events.send(AppEvent::Reload(ReloadAmount::Five)),
+            return Ok(());
+        }
+
+        //the console takes every key while it is open
+        if self.console.open {
+            match key_event.code {
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    self.events.send(AppEvent::Quit)
                 }
+                KeyCode::Char('`') | KeyCode::Esc => self.events.send(AppEvent::ToggleConsole),
+                KeyCode::Enter => {
+                    if let Some(line) = self.console.submit() {
+                        self.events.send(AppEvent::ConsoleCommand(line));
+                    }
+                }
+                KeyCode::Backspace => self.console.backspace(),
+                KeyCode::Up => self.console.history_prev(),
+                KeyCode::Down => self.console.history_next(),
+                KeyCode::Char(c) => self.console.push_char(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+        //so does the palette, like a text box
+        if self.palette.open {
+            match key_event.code {
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    self.events.send(AppEvent::Quit)
+                }
+                KeyCode::Esc => self.events.send(AppEvent::TogglePalette),
+                KeyCode::Enter => {
+                    if let Some(line) = self.palette.submit() {
+                        self.events.send(AppEvent::PaletteCommand(line));
+                    }
+                }
+                KeyCode::Tab => {
+                    let candidates = commands::complete(&self.palette.input, self);
+                    self.palette.complete(candidates);
+                }
+                KeyCode::Backspace => self.palette.backspace(),
+                KeyCode::Up => self.palette.history_prev(),
+                KeyCode::Down => self.palette.history_next(),
+                KeyCode::Char(c) => self.palette.push_char(c),
+                _ => {}
             }
-            KeyCode::Char(' ') => self.events.send(AppEvent::Shoot),
-            // Other handlers you could add here.
-            _ => {}
+            return Ok(());
+        }
+
+        //and a log search while it is being typed
+        if self.logger.searching {
+            match key_event.code {
+                KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+                    self.events.send(AppEvent::Quit)
+                }
+                KeyCode::Esc => self.logger.cancel_search(),
+                KeyCode::Enter => self.logger.finish_search(),
+                KeyCode::Backspace => self.logger.search_pop(),
+                KeyCode::Char(c) => self.logger.search_push(c),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        if let Some(event) = screens::handle_key(self, key_event) {
+            self.events.send(event);
         }
         Ok(())
     }
 
+    /// Hit-tests the mouse against what was drawn last frame and sends what it means.
     pub fn handle_mouse_events(&mut self, mouse_event: MouseEvent) -> color_eyre::Result<()> {
-        match mouse_event.kind {
-            MouseEventKind::ScrollUp => {
-                self.logger.send_log(Some("scrolling up".to_string()));
-                self.events.send(AppEvent::ScrollUp)
+        //the console and the palette are for typing
+        if self.console.open || self.palette.open {
+            return Ok(());
+        }
+        //a held window follows the mouse wherever it goes until the button is let go
+        if let Some(drag) = self.drag {
+            match mouse_event.kind {
+                MouseEventKind::Drag(MouseButton::Left) => {
+                    let bounds = self.hit_map.borrow().bounds;
+                    let area = drag.area(mouse_event.column, mouse_event.row, bounds);
+                    self.widget_data.set_area(drag.kind, Some(area));
+                },
+                MouseEventKind::Up(MouseButton::Left) => {
+                    self.drag = None;
+                    self.save_windows();
+                },
+                _ => {},
+            }
+            return Ok(());
+        }
+        if self.dialogs.is_open() {
+            if mouse_event.kind == MouseEventKind::Down(MouseButton::Left) {
+                self.dialogs.dismiss_message();
+            }
+            return Ok(());
+        }
+
+        let hits = self.hit_map.borrow().at(mouse_event.column, mouse_event.row);
+        //the help overlay covers the table, the menus have nothing to click yet
+        let target = match hits.first() {
+            Some(HitTarget::Help) => HitTarget::Help,
+            Some(_) if self.help.open => return Ok(()),
+            Some(target) if self.screen == Screen::Game => *target,
+            _ => return Ok(()),
+        };
+
+        let click = mouse_event.kind == MouseEventKind::Down(MouseButton::Left);
+        let event = match (mouse_event.kind, target) {
+            (MouseEventKind::ScrollUp, HitTarget::Help) => AppEvent::Help(HelpInput::Up),
+            (MouseEventKind::ScrollDown, HitTarget::Help) => AppEvent::Help(HelpInput::Down),
+            (MouseEventKind::ScrollUp | MouseEventKind::ScrollDown, HitTarget::Widget(kind)) => {
+                let up = mouse_event.kind == MouseEventKind::ScrollUp;
+                match self.widget_data.pane(kind).and_then(|pane| pane.scroll(up)) {
+                    Some(event) => event,
+                    None => return Ok(()),
+                }
             },
-            MouseEventKind::Drag(mouse_button) => {
-                match mouse_button {
-                    MouseButton::Left => {
-                        self.logger.send_log(Some("left dragging".to_string()));
-                    },
-                    _ => {
-                        self.logger.send_log(Some("some other dragging".to_string()));
-                    },
+            (_, HitTarget::Widget(kind)) if click => {
+                //the title bar moves the window, a corner resizes it
+                let area = self.hit_map.borrow().area_of(target);
+                if let (true, Some(area)) = (self.widget_data.pane(kind).is_some(), area) {
+                    self.drag = Drag::grab(kind, area, mouse_event.column, mouse_event.row);
                 }
+                AppEvent::Focus(kind)
             },
-            _ => {
-            }
-        }
+            (_, HitTarget::Shotgun) if click && self.aim == PLAYER => AppEvent::ShootSelf,
+            (_, HitTarget::Shotgun) if click => AppEvent::Shoot,
+            (_, HitTarget::Player(idx)) if click => AppEvent::Aim(idx),
+            (_, HitTarget::InventorySlot(slot)) if click => {
+                self.inventory.cursor = slot;
+                self.events.send(AppEvent::Focus(WidgetKind::Inventory));
+                AppEvent::UseItem(slot)
+            },
+            _ => return Ok(()),
+        };
+        self.events.send(event);
         Ok(())
     }
 
+    /// remembers where the windows are for the next session
+    fn save_windows(&mut self) {
+        match windows::save(&self.widget_data) {
+            Ok(()) => self.log(LogEntry::debug("Saved the window layout")),
+            Err(err) => self.log(LogEntry::system(format!("Could not save the window layout: {}", err)).error()),
+        }
+    }
+
     fn render_ui(&mut self, frame: &mut Frame){
-        let log: Option<String> = ui::render_ui(self, frame);
-        self.logger.send_log(log);
+        self.hit_map.borrow_mut().clear(frame.area());
+        if let Some(msg) = ui::render_ui(self, frame) {
+            self.log(LogEntry::debug(msg));
+        }
     }
 
     /// Handles the tick event of the terminal.
     ///
     /// The tick event is where you can update the state of your application with any logic that
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        if let Some(loot) = self.loot.as_mut() {
+            loot.tick();
+            if loot.done() {
+                self.loot = None;
+            }
+        }
+
+        //the dealer waits for the items to be laid out and for any dialog to be answered
+        let dealer_can_act = self.screen == Screen::Game
+            && !self.data.shotgun.is_empty()
+            && self.loot.is_none()
+            && !self.dialogs.is_open();
+        if self.data.match_data.turn == Some(DEALER) && dealer_can_act {
+            self.dealer_timer += 1;
+            if self.dealer_timer >= DEALER_DELAY {
+                self.dealer_timer = 0;
+                self.events.send(AppEvent::DealerTurn);
+            }
+        } else {
+            self.dealer_timer = 0;
+        }
+    }
+
+    fn run_debug_command(&mut self, line: &str) {
+        match DebugCommand::parse(line, &self.data) {
+            Ok(DebugCommand::Clear) => self.console.clear(),
+            Ok(DebugCommand::Dump) if !self.debug => {
+                self.console.print("dump shows the hidden shells, turn on `debug on` first".to_string());
+            },
+            Ok(DebugCommand::Debug(on)) => {
+                if on {
+                    self.mark_unscored();
+                }
+                self.debug = on;
+                self.console.print(format!("debug mode {}", if on { "on" } else { "off" }));
+            },
+            Ok(command) => {
+                //help only reads, dump is covered by `debug on`
+                if !matches!(command, DebugCommand::Help | DebugCommand::Dump) {
+                    self.mark_unscored();
+                }
+                for output in command.apply(&mut self.data) {
+                    self.console.print(output);
+                }
+                //a health change from the console can end the match like a shot would
+                let match_live = self.data.match_data.turn.is_some() && self.tutorial.is_none();
+                if let (true, Some(winner)) = (match_live, self.data.winner()) {
+                    self.end_match(winner);
+                }
+            },
+            Err(err) => self.console.print(err),
+        }
+    }
+
+    /// a run or daily the console touched can't get on the high scores or the daily record
+    fn mark_unscored(&mut self) {
+        let recorded = self.endless.is_some() || self.daily.as_ref().is_some_and(|daily| daily.scored);
+        if recorded && !self.unscored {
+            self.console.print("the console was used, this run won't be recorded".to_string());
+        }
+        self.unscored = true;
+    }
+
+    fn start_sandbox(&mut self) {
+        self.sandbox = Some(Sandbox::new());
+        self.data.new_match();
+        self.log(LogEntry::game("Sandbox: queue shells and load them from the panel"));
+    }
+
+    fn tutorial_allows(&self, event: &AppEvent) -> bool {
+        self.tutorial.as_ref().is_none_or(|tutorial| tutorial.allows(event))
+    }
+
+    fn start_tutorial(&mut self) {
+        let mut tutorial = Tutorial::new();
+        tutorial.introduce();
+        self.tutorial = Some(tutorial);
+
+        self.data.new_match();
+        self.data.players[DEALER].set_health(TUTORIAL_DEALER_HEALTH);
+        self.log(LogEntry::system("Tutorial started"));
+        self.begin_tutorial_step();
+    }
+
+    /// rigs the shotgun and hands out the items for the current step
+    fn begin_tutorial_step(&mut self) {
+        let Some(step) = self.tutorial.as_ref().and_then(|tutorial| tutorial.current()).cloned() else {
+            return;
+        };
+        if let Some(load) = step.load {
+            self.log(LogEntry::game(format!("Loaded {} shells", load.len())));
+            self.data.shotgun.load_exact(load);
+        }
+        //every step starts with only its own items, so they all fit in the slots
+        self.data.players[PLAYER].clear_items();
+        for item in step.items {
+            if let Err(msg) = self.data.players[PLAYER].give_item(item, self.data.rules.inventory_size) {
+                self.log(LogEntry::debug(msg));
+            }
+        }
+        //the dealer sits the lesson out
+        self.data.match_data.turn = Some(PLAYER);
+    }
+
+    fn advance_tutorial(&mut self) {
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return;
+        };
+        if tutorial.advance().is_some() {
+            self.begin_tutorial_step();
+        } else {
+            self.tutorial = None;
+            self.log(LogEntry::system("Tutorial finished"));
+            self.start_match();
+        }
+    }
+
+    /// switches to `preset` and deals a fresh match with it, any endless run is abandoned
+    fn apply_preset(&mut self, preset: Preset) {
+        if self.daily.is_some() {
+            self.log(LogEntry::system("The daily challenge always uses the standard preset").warning());
+            return;
+        }
+        self.data.rules = preset.rules.clone();
+        self.endless = None;
+        self.screen = Screen::Game;
+        //straight to the panes, the event would be dropped once the new preset has no log
+        if !preset.log && self.widget_data.is_displayed(WidgetKind::Log) {
+            self.widget_data.toggle_shown(WidgetKind::Log);
+        }
+        self.log(LogEntry::system(format!("Playing the {} preset", preset.name)));
+        self.preset = preset;
+        if self.sandbox.is_some() {
+            self.data.new_match();
+            self.data.shotgun.model = self.preset.model;
+        } else {
+            self.start_match();
+        }
+    }
+
+    /// drops whatever game was going and starts a new one in `mode`
+    pub fn start_game(&mut self, mode: GameMode, preset: Preset) {
+        self.abandon_game();
+        self.mode = mode;
+        self.data.rules = preset.rules.clone();
+        self.preset = preset;
+        self.screen = Screen::Game;
+        match mode {
+            GameMode::Match => self.start_match(),
+            GameMode::Daily => {
+                self.start_daily();
+                self.start_match();
+            },
+            GameMode::Tutorial => self.start_tutorial(),
+            GameMode::Sandbox => self.start_sandbox(),
+        }
+    }
+
+    /// forgets the run, lesson or sandbox being played, nothing is recorded
+    pub fn abandon_game(&mut self) {
+        //debug mode shows the hidden shells, nothing played with it on is recorded
+        self.unscored = self.debug;
+        //a seed from the console or a daily only holds for that game
+        self.data.reseed(thread_rng().r#gen());
+        self.endless = None;
+        self.daily = None;
+        self.tutorial = None;
+        self.sandbox = None;
+        self.loot = None;
+    }
+
+    /// the daily is an endless run where every stage is seeded from the date
+    fn start_daily(&mut self) {
+        let daily = DailyRun::start();
+        //a custom rules file or preset would give a different game than the rest of the team
+        if self.preset != Preset::default() {
+            self.preset = Preset::default();
+            self.data.rules = Rules::default();
+            self.log(LogEntry::system("The daily challenge always uses the standard rules").warning());
+        }
+        if daily.scored && self.unscored {
+            self.log(LogEntry::system("Debug mode is on, this daily run won't be recorded").warning());
+        } else if daily.scored {
+            self.log(LogEntry::system(format!("Daily challenge for {}, good luck", daily.challenge.date)));
+            //finishing the run overwrites this with the real result
+            if let Err(err) = daily.abandoned().save() {
+                self.log(LogEntry::system(format!("Could not save the daily result: {}", err)).error());
+            }
+        } else {
+            if let Some(record) = DailyRecord::load(&daily.challenge.date) {
+                self.log(LogEntry::system(format!("Already played today: {}", record.result)));
+                self.log(LogEntry::system(record.share));
+            }
+            self.log(LogEntry::system("This daily run is practice and won't be recorded"));
+        }
+        self.daily = Some(daily);
+        self.endless = Some(EndlessRun::new());
+    }
+
+    /// records the daily result and returns the lines for the game over screen
+    fn finish_daily(&mut self, run: &EndlessRun, died: bool) -> Vec<String> {
+        let Some(daily) = self.daily.take() else {
+            return Vec::new();
+        };
+        let (stages_cleared, score, result) = if died {
+            (run.stage - 1, 0, format!("died at stage {}", run.stage))
+        } else {
+            (run.stage, run.score, format!("cashed out {} points after stage {}", run.score, run.stage))
+        };
+        let share = daily.share_string(stages_cleared, score, died);
+
+        if daily.scored && !self.unscored {
+            let record = DailyRecord {
+                date: daily.challenge.date.clone(),
+                result: result.clone(),
+                share: share.clone(),
+            };
+            if let Err(err) = record.save() {
+                self.log(LogEntry::system(format!("Could not save the daily result: {}", err)).error());
+            }
+            self.log(LogEntry::system(share.clone()));
+        } else {
+            self.log(LogEntry::system(format!("Practice daily over: {}", result)));
+        }
+
+        //back to regular, unseeded games
+        self.data.reseed(thread_rng().r#gen());
+        vec![format!("Daily {}: {}", daily.challenge.date, result), share]
+    }
+
+    /// resets the table and loads the shotgun for the next match
+    pub fn start_match(&mut self) {
+        self.data.new_match();
+        self.data.shotgun.model = self.preset.model;
+        if let (Some(daily), Some(run)) = (&self.daily, &self.endless) {
+            self.data.reseed(daily.challenge.stage_seed(run.stage));
+        }
+        let msg = match &self.endless {
+            Some(run) => format!("Stage {} begins, score {} at x{}", run.stage, run.score, run.multiplier),
+            None => "A new match begins".to_string(),
+        };
+        self.log(LogEntry::game(msg));
+        self.reload(self.reload_amount());
+    }
+
+    /// shells for the next reload, escalating with the endless stage if a run is going
+    pub fn reload_amount(&self) -> ReloadAmount {
+        match &self.endless {
+            Some(run) => run.reload_amount(),
+            None => ReloadAmount::for_round(self.data.match_data.round_count + 1),
+        }
+    }
+
+    fn reload(&mut self, amount: ReloadAmount) {
+        for msg in self.data.end_round() {
+            self.log(LogEntry::game(msg));
+        }
+        //the AED can finish someone off between rounds
+        if let Some(winner) = self.data.winner() {
+            self.end_match(winner);
+            return;
+        }
+        let exotic_bonus = self.endless.as_ref().map_or(0, |run| run.exotic_bonus());
+        let num_shells = self.data.rules.reload_size(amount);
+        let mut loaded = self.load_shells(&self.data.rules.clone(), num_shells, exotic_bonus);
+        //keep playing on the standard shells, the player can see what went wrong
+        if let Err(err) = &loaded {
+            let msg = format!("Could not load the shotgun: {}, using the standard shells instead", err);
+            self.log(LogEntry::game(msg.clone()).error());
+            self.dialogs.push(Dialog::message("Error", msg));
+            loaded = self.load_shells(&Rules::default(), num_shells, 0);
+        }
+        match loaded {
+            Ok(()) => {
+                self.data.match_data.increment_round();
+                self.log(LogEntry::game(format!(
+                    "Loaded {} shells: {}", num_shells, self.data.shotgun.announcement()
+                )));
+                self.deal_items();
+            },
+            //nothing left to shoot with, the match can't go on
+            Err(err) => {
+                let msg = format!("Could not load the shotgun: {}", err);
+                self.log(LogEntry::game(msg.clone()).error());
+                self.abandon_game();
+                self.data.match_data.turn = None;
+                self.screen = Screen::GameOver { lines: vec![msg], cursor: 0 };
+            },
+        }
+    }
+
+    /// fills the shotgun from `rules`, the way the preset asks for
+    fn load_shells(&mut self, rules: &Rules, num_shells: usize, exotic_bonus: usize) -> Result<(), ShotgunError> {
+        match self.preset.shells {
+            ShellSource::Random => self.data.shotgun.load_escalated_shells(
+                &mut self.data.rng, &rules.random_shells, num_shells, exotic_bonus
+            ),
+            ShellSource::Classic => self.data.shotgun.load_default_shells(
+                &mut self.data.rng, &rules.default_shells, num_shells
+            ),
+        }
+    }
+
+    /// hands out the items for this round and starts laying them out
+    fn deal_items(&mut self) {
+        let deals = self.data.deal_items();
+        for deal in &deals {
+            let name = self.data.players[deal.player].name();
+            let received = deal.items.iter().map(|item| format!("{:?}", item)).collect::<Vec<_>>().join(", ");
+            let msg = match (deal.items.is_empty(), deal.capped) {
+                (false, false) => format!("{} received {}", name, received),
+                (false, true) => format!("{} received {}, the inventory is full", name, received),
+                (true, true) => format!("{} received nothing, the inventory is full", name),
+                (true, false) => continue,
+            };
+            self.log(LogEntry::game(msg));
+        }
+        if deals.iter().any(|deal| !deal.items.is_empty()) {
+            self.loot = Some(LootAnimation::new(deals));
+        }
+    }
+
+    /// uses an item and, like a shot would, reloads once the shotgun runs dry
+    fn use_item(&mut self, user: usize, slot: usize) {
+        let result = self.data.use_item(user, slot);
+        self.item_used(result);
+    }
+
+    /// Adrenaline lets the player pick what to steal when there is a choice, returns whether it asked
+    fn ask_steal(&mut self, slot: usize) -> bool {
+        if self.data.players[PLAYER].items().get(slot) != Some(&Items::Adrenaline) {
+            return false;
+        }
+        let mut options: Vec<(String, AppEvent)> = Vec::new();
+        for (target, item) in self.data.players[DEALER].items().iter().enumerate() {
+            if *item == Items::Adrenaline || options.iter().any(|(name, _)| name == item.name()) {
+                continue;
+            }
+            options.push((item.name().to_string(), AppEvent::Steal { slot, target }));
+        }
+        if options.len() < 2 {
+            return false;
+        }
+        let body = format!("Steal which of the {}'s items?", self.data.players[DEALER].name());
+        self.dialogs.push(Dialog::choice("Adrenaline", body, options));
+        true
+    }
+
+    /// logs what an item did and reloads if it emptied the shotgun
+    fn item_used(&mut self, result: Result<Vec<String>, String>) {
+        let log = match result {
+            Ok(log) => log,
+            Err(msg) => {
+                self.log(LogEntry::game(msg).warning());
+                return;
+            },
+        };
+        for msg in log {
+            self.log(LogEntry::game(msg));
+        }
+        if self.data.shotgun.is_empty() && self.sandbox.is_some() {
+            self.log(LogEntry::game("The shotgun is empty, load it from the sandbox panel"));
+        } else if self.data.shotgun.is_empty() {
+            self.reload(self.reload_amount());
+        }
+    }
+
+    /// fires a shell and moves the match along: a winner ends it, an empty shotgun reloads
+    fn take_shot(&mut self, shooter: usize, target: usize) {
+        //the turn has moved on by the time the shot is logged, but it was the shooter's
+        let turn = self.data.players[shooter].name().to_string();
+        for msg in self.data.fire(shooter, target) {
+            self.log(LogEntry::game(msg).during(&turn));
+        }
+
+        //the tutorial rigs every load itself and can't be lost
+        if self.tutorial.is_some() {
+            return;
+        }
+        if let Some(winner) = self.data.winner() {
+            self.end_match(winner);
+        } else if self.data.shotgun.is_empty() && self.sandbox.is_some() {
+            self.log(LogEntry::game("The shotgun is empty, load it from the sandbox panel"));
+        } else if self.data.shotgun.is_empty() {
+            self.reload(self.reload_amount());
+        }
+    }
+
+    fn end_match(&mut self, winner: usize) {
+        self.data.match_data.turn = None;
+        if self.sandbox.is_some() {
+            let msg = format!("{} wins, the sandbox table is reset", self.data.players[winner].name());
+            self.log(LogEntry::game(msg));
+            self.data.new_match();
+            return;
+        }
+        if winner == PLAYER {
+            let health_left = self.data.players[PLAYER].health();
+            let run = self.endless.get_or_insert_with(EndlessRun::new);
+            let earned = run.bank_win(health_left);
+            let msg = format!("You win stage {} for {} points, double or nothing? (y/n)", run.stage, earned);
+            self.log(LogEntry::game(msg));
+            self.screen = Screen::Summary;
+        } else {
+            let mut lines = Vec::new();
+            if let Some(run) = self.endless.take() {
+                let msg = format!("You died at stage {} and lost {} points", run.stage, run.score);
+                self.log(LogEntry::game(msg.clone()));
+                lines.push(msg);
+                lines.extend(self.finish_daily(&run, true));
+            } else {
+                self.log(LogEntry::game("The dealer wins"));
+                lines.push("The dealer wins".to_string());
+            }
+            self.screen = Screen::GameOver { lines, cursor: 0 };
+        }
+    }
+
+    fn double_or_nothing(&mut self, keep_going: bool) {
+        if self.screen != Screen::Summary {
+            return;
+        }
+
+        if keep_going {
+            if let Some(run) = self.endless.as_mut() {
+                run.advance();
+            }
+            self.screen = Screen::Game;
+            self.start_match();
+            return;
+        }
+        let mut lines = Vec::new();
+        if let Some(run) = self.endless.take() {
+            if self.daily.is_some() {
+                lines = self.finish_daily(&run, false);
+            } else if self.unscored {
+                let msg = format!("Cashed out {} points, not recorded after using the console or debug mode", run.score);
+                self.log(LogEntry::game(msg.clone()));
+                lines.push(msg);
+            } else {
+                let msg = match self.high_scores.record(run.score, run.stage) {
+                    Some(rank) => format!("Cashed out {} points, #{} on the high scores", run.score, rank),
+                    None => format!("Cashed out {} points", run.score),
+                };
+                self.log(LogEntry::game(msg.clone()));
+                lines.push(msg);
+                if let Err(err) = self.high_scores.save() {
+                    self.log(LogEntry::system(format!("Could not save high scores: {}", err)).error());
+                }
+            }
+        }
+        self.screen = Screen::GameOver { lines, cursor: 0 };
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
         self.running = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screens::MenuInput;
+
+    #[tokio::test]
+    async fn a_debug_run_never_writes_a_record() {
+        let dir = std::env::temp_dir().join(format!("cli-roulette-test-{}", std::process::id()));
+        //nothing else in the tests reads or writes the config directory
+        unsafe { std::env::set_var("XDG_CONFIG_HOME", &dir) };
+        let saved = || ["daily", "highscores"].map(|file| dir.join("cli-roulette").join(file).exists());
+
+        let args = Args { daily: true, debug: true, ..Args::default() };
+        let mut app = App::new(args, Vec::new(), Preset::default(), Keymap::new());
+        assert!(app.daily.as_ref().is_some_and(|daily| daily.scored) && app.unscored);
+        app.screen = Screen::Summary;
+        app.double_or_nothing(false);
+        assert_eq!(saved(), [false, false]);
+
+        let args = Args { debug: true, preset: Some("Standard".to_string()), ..Args::default() };
+        let mut app = App::new(args, Vec::new(), Preset::default(), Keymap::new());
+        let mut run = EndlessRun::new();
+        run.bank_win(3);
+        app.endless = Some(run);
+        app.screen = Screen::Summary;
+        app.double_or_nothing(false);
+        assert_eq!(saved(), [false, false]);
+        assert!(app.high_scores.entries.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn play_again_starts_the_same_mode_from_scratch() {
+        let mut app = App::default();
+        app.start_game(GameMode::Sandbox, Preset::default());
+        app.unscored = true;
+        app.screen = Screen::GameOver { lines: Vec::new(), cursor: 0 };
+        screens::update(&mut app, MenuInput::Select);
+        assert_eq!(app.screen, Screen::Game);
+        assert!(app.sandbox.is_some());
+        assert!(!app.unscored);
+    }
+}
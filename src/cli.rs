@@ -0,0 +1,55 @@
+//cli.rs
+use std::path::PathBuf;
+
+use color_eyre::eyre::{bail, OptionExt};
+
+const USAGE: &str = "usage: cli-roulette [--daily | --tutorial | --sandbox] [--debug] [--rules <file>] [--preset <name>]";
+
+/// Command line flags.
+#[derive(Debug, Default, Clone)]
+pub struct Args {
+    /// play today's daily challenge
+    pub daily: bool,
+    /// walk through the rigged tutorial
+    pub tutorial: bool,
+    /// practice table where shells, items and health are set by hand
+    pub sandbox: bool,
+    /// show secret state, like the shell order, in the data inspector
+    pub debug: bool,
+    /// rules file to play with instead of the one in the config directory
+    pub rules: Option<PathBuf>,
+    /// built-in or saved preset to start with
+    pub preset: Option<String>,
+}
+
+impl Args {
+    pub fn parse() -> color_eyre::Result<Args> {
+        let mut args = Args::default();
+        let mut argv = std::env::args().skip(1);
+        while let Some(arg) = argv.next() {
+            match arg.as_str() {
+                "--daily" => args.daily = true,
+                "--tutorial" => args.tutorial = true,
+                "--sandbox" => args.sandbox = true,
+                "--debug" => args.debug = true,
+                "--rules" => {
+                    let path = argv.next().ok_or_eyre(format!("--rules needs a file\n{}", USAGE))?;
+                    args.rules = Some(PathBuf::from(path));
+                }
+                "--preset" => {
+                    let name = argv.next().ok_or_eyre(format!("--preset needs a name\n{}", USAGE))?;
+                    args.preset = Some(name);
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    std::process::exit(0);
+                }
+                other => bail!("unknown argument `{}`\n{}", other, USAGE),
+            }
+        }
+        if [args.daily, args.tutorial, args.sandbox].iter().filter(|mode| **mode).count() > 1 {
+            bail!("only one of --daily, --tutorial and --sandbox can be used\n{}", USAGE);
+        }
+        Ok(args)
+    }
+}
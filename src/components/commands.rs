@@ -0,0 +1,206 @@
+//commands.rs
+//lines typed into the ':' palette, each one becomes the same `AppEvent` a key would send
+use std::path::PathBuf;
+
+use crate::app::App;
+use crate::components::enums::parse_variant;
+use crate::components::items::Items;
+use crate::data::PLAYER;
+use crate::event::AppEvent;
+use crate::keymap::{Action, Keymap};
+use crate::screens;
+
+/// where `log save` writes without a file name, relative to where the game was started
+pub const LOG_FILE: &str = "cli-roulette.log";
+
+/// the commands that take arguments, everything else is an action name from the keymap
+const COMMANDS: [&str; 3] = ["shoot", "use", "log"];
+const SHOOT_TARGETS: [&str; 2] = ["dealer", "self"];
+
+/// parses one palette line, the error is ready to show under it
+pub fn parse(line: &str, app: &App) -> Result<AppEvent, String> {
+    let mut words = line.split_whitespace();
+    let Some(name) = words.next() else {
+        return Err("type a command, tab completes".to_string());
+    };
+    let name = name.to_lowercase();
+    let args: Vec<&str> = words.collect();
+
+    let event = match (name.as_str(), args.as_slice()) {
+        ("shoot", ["dealer" | "them"]) => AppEvent::Shoot,
+        ("shoot", ["self" | "me" | "myself"]) => AppEvent::ShootSelf,
+        ("shoot", [target]) => return Err(format!("can't shoot `{}`, try dealer or self", target)),
+        ("use", [word]) => {
+            if let Ok(number) = word.parse::<usize>() {
+                //only the slots the inventory draws
+                if number == 0 || number > app.data.rules.inventory_size {
+                    return Err(format!("there is no slot {}", word));
+                }
+                AppEvent::UseItem(number - 1)
+            } else {
+                use_item(word, app)?
+            }
+        },
+        ("use", item) if !item.is_empty() => use_item(&item.join(" "), app)?,
+        ("log", ["save"]) => AppEvent::SaveLog(None),
+        ("log", ["save", path]) => AppEvent::SaveLog(Some(PathBuf::from(path))),
+        (name, []) => match actions(app).into_iter().find(|(action_name, _)| action_name == name) {
+            Some((_, action)) => action.event(app),
+            None => return Err(format!("unknown command `{}`, tab lists them", name)),
+        },
+        (name, _) if COMMANDS.contains(&name) || actions(app).iter().any(|(action_name, _)| action_name == name) => {
+            return Err(format!("wrong arguments for `{}`", name));
+        },
+        (name, _) => return Err(format!("unknown command `{}`, tab lists them", name)),
+    };
+    Ok(event)
+}
+
+/// uses the first held item called `item`
+fn use_item(item: &str, app: &App) -> Result<AppEvent, String> {
+    let Some(wanted) = parse_variant(item, &Items::ALL) else {
+        return Err(format!("unknown item `{}`", item));
+    };
+    match app.data.players[PLAYER].items().iter().position(|held| *held == wanted) {
+        Some(slot) => Ok(AppEvent::UseItem(slot)),
+        None => Err(format!("you don't have a {}", wanted.name())),
+    }
+}
+
+/// what the word at the end of `line` could be completed to
+pub fn complete(line: &str, app: &App) -> Vec<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let typing = if line.ends_with(' ') || line.is_empty() { "" } else { words.last().copied().unwrap_or("") };
+    let done = if typing.is_empty() { words.len() } else { words.len() - 1 };
+
+    let options: Vec<String> = match (done, words.first().map(|word| word.to_lowercase())) {
+        (0, _) => {
+            let mut names: Vec<String> = actions(app).into_iter().map(|(name, _)| name).collect();
+            names.extend(COMMANDS.iter().map(|name| name.to_string()));
+            names.sort();
+            names.dedup();
+            names
+        },
+        (1, Some(name)) if name == "shoot" => SHOOT_TARGETS.iter().map(|target| target.to_string()).collect(),
+        (1, Some(name)) if name == "use" => {
+            let mut items: Vec<String> = app.data.players[PLAYER].items()
+                .iter()
+                .map(|item| item.name().to_lowercase().replace(' ', "_"))
+                .collect();
+            items.sort();
+            items.dedup();
+            items
+        },
+        (1, Some(name)) if name == "log" => vec!["save".to_string()],
+        _ => Vec::new(),
+    };
+    options.into_iter().filter(|option| option.starts_with(&typing.to_lowercase())).collect()
+}
+
+/// every action of the screen that is up, by name, whether or not it is bound to a key
+fn actions(app: &App) -> Vec<(String, Action)> {
+    let defaults = Keymap::new();
+    let mut actions: Vec<(String, Action)> = Vec::new();
+    for context in screens::contexts(app) {
+        for binding in defaults.bindings.iter().filter(|binding| binding.context == context) {
+            //the palette is already open
+            if matches!(binding.action, Action::TogglePalette) {
+                continue;
+            }
+            let name = binding.action.name();
+            //the first context wins, like it does for keys
+            if !actions.iter().any(|(existing, _)| *existing == name) {
+                actions.push((name, binding.action));
+            }
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::MAX_INVENTORY_SIZE;
+    use crate::screens::Screen;
+
+    //the event handler needs a runtime, so these run under tokio
+    fn table() -> App {
+        let mut app = App::default();
+        app.screen = Screen::Game;
+        app
+    }
+
+    #[tokio::test]
+    async fn parses_shooting_at_either_target() {
+        let app = table();
+        assert!(matches!(parse("shoot dealer", &app), Ok(AppEvent::Shoot)));
+        assert!(matches!(parse("SHOOT self", &app), Ok(AppEvent::ShootSelf)));
+        assert!(matches!(parse("  shoot   me ", &app), Ok(AppEvent::ShootSelf)));
+        assert!(parse("shoot the moon", &app).is_err());
+        //on its own it is the action bound to space, which shoots the dealer too
+        assert!(matches!(parse("shoot", &app), Ok(AppEvent::Shoot)));
+    }
+
+    #[tokio::test]
+    async fn uses_items_by_slot_or_by_name() {
+        let mut app = table();
+        app.data.players[PLAYER].give_item(Items::Beer, MAX_INVENTORY_SIZE).unwrap();
+        app.data.players[PLAYER].give_item(Items::MagnifyingGlass, MAX_INVENTORY_SIZE).unwrap();
+        assert!(matches!(parse("use 2", &app), Ok(AppEvent::UseItem(1))));
+        assert!(matches!(parse("use magnifying glass", &app), Ok(AppEvent::UseItem(1))));
+        assert!(matches!(parse("use beer", &app), Ok(AppEvent::UseItem(0))));
+        assert_eq!(parse("use 0", &app).unwrap_err(), "there is no slot 0");
+        assert_eq!(parse("use 9", &app).unwrap_err(), "there is no slot 9");
+        app.data.rules.inventory_size = 4;
+        assert_eq!(parse("use 5", &app).unwrap_err(), "there is no slot 5");
+        assert_eq!(parse("use saw", &app).unwrap_err(), "you don't have a Saw");
+        assert_eq!(parse("use spoon", &app).unwrap_err(), "unknown item `spoon`");
+    }
+
+    #[tokio::test]
+    async fn parses_log_saving() {
+        let app = table();
+        assert!(matches!(parse("log save", &app), Ok(AppEvent::SaveLog(None))));
+        match parse("log save run.log", &app) {
+            Ok(AppEvent::SaveLog(Some(path))) => assert_eq!(path, PathBuf::from("run.log")),
+            other => panic!("expected a log file, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn action_names_only_work_on_their_screen() {
+        let mut app = table();
+        assert!(matches!(parse("odds", &app), Ok(AppEvent::ShowOdds)));
+        assert!(matches!(parse("reload", &app), Ok(AppEvent::Reload(_))));
+        assert_eq!(parse("odds now", &app).unwrap_err(), "wrong arguments for `odds`");
+        assert_eq!(parse("dance", &app).unwrap_err(), "unknown command `dance`, tab lists them");
+        assert!(parse("", &app).is_err());
+
+        app.screen = Screen::Title { cursor: 0 };
+        assert!(parse("odds", &app).is_err());
+    }
+
+    #[tokio::test]
+    async fn completes_command_names() {
+        let app = table();
+        let all = complete("", &app);
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]), "sorted without duplicates");
+        assert!(all.contains(&"shoot".to_string()) && all.contains(&"reload".to_string()));
+        assert!(!all.contains(&"palette".to_string()));
+        assert_eq!(complete("sh", &app), vec!["shoot".to_string(), "shoot_self".to_string()]);
+        assert_eq!(complete("LO", &app), vec!["log".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn completes_arguments() {
+        let mut app = table();
+        app.data.players[PLAYER].give_item(Items::MagnifyingGlass, MAX_INVENTORY_SIZE).unwrap();
+        app.data.players[PLAYER].give_item(Items::Beer, MAX_INVENTORY_SIZE).unwrap();
+        app.data.players[PLAYER].give_item(Items::Beer, MAX_INVENTORY_SIZE).unwrap();
+        assert_eq!(complete("shoot ", &app), vec!["dealer".to_string(), "self".to_string()]);
+        assert_eq!(complete("shoot s", &app), vec!["self".to_string()]);
+        assert_eq!(complete("use ", &app), vec!["beer".to_string(), "magnifying_glass".to_string()]);
+        assert_eq!(complete("log s", &app), vec!["save".to_string()]);
+        assert!(complete("log save ", &app).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//daily.rs
+//one shared game per day: everything random is seeded from the date, so the whole team
+//faces the same shells and the same dealer
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::config_dir;
+
+const DAILY_FILE: &str = "daily";
+
+#[derive(Debug, Clone)]
+pub struct DailyChallenge {
+    /// `YYYY-MM-DD`, in UTC so everyone agrees on what "today" is
+    pub date: String,
+    pub seed: u64,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() / 86_400)
+            .unwrap_or(0);
+        Self::for_date(civil_date(days as i64))
+    }
+
+    pub fn for_date(date: String) -> Self {
+        let seed = fnv1a(date.as_bytes());
+        DailyChallenge { date, seed }
+    }
+
+    /// every stage gets its own seed, so a stage starts the same no matter how the last one went
+    pub fn stage_seed(&self, stage: usize) -> u64 {
+        //the same eight bytes on every platform, so a 32-bit build plays the same daily
+        self.seed ^ fnv1a(&(stage as u64).to_le_bytes())
+    }
+}
+
+/// A daily attempt in progress.
+#[derive(Debug, Clone)]
+pub struct DailyRun {
+    pub challenge: DailyChallenge,
+    /// only the first attempt of the day counts
+    pub scored: bool,
+}
+
+impl DailyRun {
+    pub fn start() -> Self {
+        let challenge = DailyChallenge::today();
+        let scored = DailyRecord::load(&challenge.date).is_none();
+        DailyRun { challenge, scored }
+    }
+
+    /// a short line to paste into chat, one square per stage cleared
+    pub fn share_string(&self, stages_cleared: usize, score: u64, died: bool) -> String {
+        let mut squares = "🟩".repeat(stages_cleared);
+        squares.push_str(if died { "💀" } else { "💰" });
+        format!("cli-roulette daily {}: {} {} pts", self.challenge.date, squares, score)
+    }
+
+    /// what is kept for the day until the run finishes, so quitting halfway doesn't earn a retry
+    pub fn abandoned(&self) -> DailyRecord {
+        DailyRecord {
+            date: self.challenge.date.clone(),
+            result: "abandoned".to_string(),
+            share: format!("cli-roulette daily {}: abandoned", self.challenge.date),
+        }
+    }
+}
+
+/// The scored result of one day, stored as tab separated `date result share` lines.
+#[derive(Debug, Clone)]
+pub struct DailyRecord {
+    pub date: String,
+    pub result: String,
+    pub share: String,
+}
+
+impl DailyRecord {
+    /// the record for `date`, if that day has been played
+    pub fn load(date: &str) -> Option<DailyRecord> {
+        let path = config_dir()?.join(DAILY_FILE);
+        fs::read_to_string(path)
+            .ok()?
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                Some(DailyRecord {
+                    date: parts.next()?.to_string(),
+                    result: parts.next()?.to_string(),
+                    share: parts.next()?.to_string(),
+                })
+            })
+            .find(|record| record.date == date)
+    }
+
+    /// adds the record, replacing one already saved for the same date
+    pub fn save(&self) -> std::io::Result<()> {
+        let dir = config_dir().ok_or_else(|| std::io::Error::other("no config directory"))?;
+        fs::create_dir_all(&dir)?;
+        let path = dir.join(DAILY_FILE);
+        let mut contents: String = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| line.split('\t').next() != Some(self.date.as_str()))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        contents.push_str(&format!("{}\t{}\t{}\n", self.date, self.result, self.share));
+        fs::write(path, contents)
+    }
+}
+
+/// days since the unix epoch to a `YYYY-MM-DD` date
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+//a fixed hash, std's hasher is allowed to change between releases which would break seeds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_seeds_are_the_same_on_every_platform() {
+        //worked out once by hand, a 32-bit build has to land on the same numbers
+        let challenge = DailyChallenge::for_date("2026-10-19".to_string());
+        assert_eq!(challenge.stage_seed(1), 0xbe05_0b13_67b5_efbc);
+        assert_eq!(challenge.stage_seed(2), 0xd175_bc7e_4767_ce1f);
+    }
+}
@@ -0,0 +1,60 @@
+//dealer.rs
+use rand::Rng;
+
+use crate::components::shotgun::Shell;
+
+/// Decides whether the dealer shoots itself, from the dealer's own `odds` for the next shell.
+///
+/// The odds are worked out the way the HUD does for the player, from the announced load
+/// and what the dealer has seen, so an Imposter passes for a Blank and an Inverter the
+/// player used leaves the dealer guessing. A shell the dealer has seen decides it outright.
+pub fn shoots_self(rng: &mut impl Rng, odds: &[(Shell, f64)]) -> bool {
+    let blank = odds.iter()
+        .filter(|(shell, _)| *shell == Shell::Blank)
+        .map(|(_, chance)| chance)
+        .sum::<f64>();
+
+    if odds.is_empty() || (blank - 0.5).abs() < f64::EPSILON {
+        rng.gen_bool(0.5)
+    } else {
+        blank > 0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::components::knowledge::Knowledge;
+    use crate::components::odds::next_shell_odds;
+    use crate::components::shotgun::Shotgun;
+
+    fn decide(shotgun: &Shotgun, knowledge: &Knowledge) -> bool {
+        shoots_self(&mut StdRng::seed_from_u64(1), &next_shell_odds(shotgun, knowledge))
+    }
+
+    #[test]
+    fn an_imposter_passes_for_a_blank() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Imposter, Shell::Imposter, Shell::Live]);
+        assert!(decide(&shotgun, &Knowledge::new()));
+    }
+
+    #[test]
+    fn an_inverted_shell_is_not_seen_through() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Blank, Shell::Blank, Shell::Live]);
+        //the player turned the next Blank into a Live, the dealer still believes the load
+        shotgun.invert();
+        assert!(decide(&shotgun, &Knowledge::new()));
+    }
+
+    #[test]
+    fn a_mostly_live_load_goes_at_the_player() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Live, Shell::Live, Shell::Blank]);
+        assert!(!decide(&shotgun, &Knowledge::new()));
+    }
+}
@@ -0,0 +1,149 @@
+//debug_commands.rs
+//commands typed into the developer console, they poke at `Data` directly
+use crate::components::enums::parse_variant;
+use crate::components::items::Items;
+use crate::components::shotgun::{Shell, ShotgunModel, ShotgunState};
+use crate::components::status::StatusKind;
+use crate::data::Data;
+
+pub const DEBUG_HELP: [&str; 11] = [
+    "give <player> <item>    hand an item to a player, e.g. give 1 saw",
+    "load <shell>...         load exactly these shells, first fires first",
+    "hp <player> <health>    set a player's health",
+    "status <player> <kind>  apply a status effect, e.g. status 2 stunned",
+    "state <state>           set the shotgun state, e.g. state rusty",
+    "model <model>           set the shotgun model",
+    "seed <number>           reseed every random decision",
+    "dump                    print the whole game state",
+    "debug <on|off>          show secrets in the data inspector",
+    "clear                   clear the console",
+    "players are 1 for you and 2 for the dealer, or their names",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugCommand {
+    Give { player: usize, item: Items },
+    Load(Vec<Shell>),
+    Health { player: usize, health: u8 },
+    Status { player: usize, kind: StatusKind },
+    State(ShotgunState),
+    Model(ShotgunModel),
+    Seed(u64),
+    Dump,
+    /// handled by the app, it isn't part of `Data`
+    Debug(bool),
+    Clear,
+    Help,
+}
+
+impl DebugCommand {
+    /// parses one console line, the error is ready to print back
+    pub fn parse(line: &str, data: &Data) -> Result<DebugCommand, String> {
+        let mut words = line.split_whitespace();
+        let Some(name) = words.next() else {
+            return Err("type `help` for a list of commands".to_string());
+        };
+        let args: Vec<&str> = words.collect();
+
+        let command = match (name.to_lowercase().as_str(), args.as_slice()) {
+            ("give", [player, item]) => DebugCommand::Give {
+                player: parse_player(player, data)?,
+                item: parse_variant(item, &Items::ALL).ok_or_else(|| format!("unknown item `{}`", item))?,
+            },
+            ("load", shells) if !shells.is_empty() => DebugCommand::Load(
+                shells.iter()
+                    .map(|shell| parse_variant(shell, &Shell::ALL).ok_or_else(|| format!("unknown shell `{}`", shell)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            ("hp", [player, health]) => DebugCommand::Health {
+                player: parse_player(player, data)?,
+                health: health.parse().map_err(|_| format!("`{}` is not a health value", health))?,
+            },
+            ("status", [player, kind]) => DebugCommand::Status {
+                player: parse_player(player, data)?,
+                kind: parse_variant(kind, &StatusKind::ALL).ok_or_else(|| format!("unknown status `{}`", kind))?,
+            },
+            ("state", [state]) => DebugCommand::State(
+                parse_variant(state, &ShotgunState::ALL).ok_or_else(|| format!("unknown state `{}`", state))?,
+            ),
+            ("model", [model]) => DebugCommand::Model(
+                parse_variant(model, &ShotgunModel::ALL).ok_or_else(|| format!("unknown model `{}`", model))?,
+            ),
+            ("seed", [seed]) => DebugCommand::Seed(
+                seed.parse().map_err(|_| format!("`{}` is not a number", seed))?,
+            ),
+            ("dump", []) => DebugCommand::Dump,
+            ("debug", ["on"]) => DebugCommand::Debug(true),
+            ("debug", ["off"]) => DebugCommand::Debug(false),
+            ("clear", []) => DebugCommand::Clear,
+            ("help", []) => DebugCommand::Help,
+            ("give" | "load" | "hp" | "status" | "state" | "model" | "seed" | "dump" | "debug" | "clear" | "help", _) => {
+                return Err(format!("wrong arguments for `{}`, type `help`", name));
+            },
+            _ => return Err(format!("unknown command `{}`, type `help`", name)),
+        };
+        Ok(command)
+    }
+
+    /// runs the command, returning the lines to print in the console
+    pub fn apply(self, data: &mut Data) -> Vec<String> {
+        match self {
+            DebugCommand::Give { player, item } => {
+                match data.players[player].give_item(item, data.rules.inventory_size) {
+                    Ok(()) => vec![format!("gave {:?} to {}", item, data.players[player].name())],
+                    Err(msg) => vec![msg],
+                }
+            },
+            DebugCommand::Load(shells) => {
+                let msg = format!("loaded {:?}", shells);
+                data.shotgun.load_exact(shells);
+                vec![msg]
+            },
+            DebugCommand::Health { player, health } => {
+                data.players[player].set_health(health);
+                vec![format!("{} health set to {}", data.players[player].name(), health)]
+            },
+            DebugCommand::Status { player, kind } => {
+                data.players[player].add_status(kind);
+                vec![format!("{} is {}", data.players[player].name(), kind.describe())]
+            },
+            DebugCommand::State(state) => {
+                data.shotgun.state = state;
+                vec![format!("shotgun state set to {:?}", state)]
+            },
+            DebugCommand::Model(model) => {
+                data.shotgun.model = model;
+                vec![format!("shotgun model set to {:?}", model)]
+            },
+            DebugCommand::Seed(seed) => {
+                data.reseed(seed);
+                vec![format!("reseeded with {}", seed)]
+            },
+            //everything but the rng, whose internal state is pages of noise
+            DebugCommand::Dump => {
+                let dump = format!(
+                    "seed: {}\n{:#?}\n{:#?}\n{:#?}",
+                    data.seed, data.shotgun, data.match_data, data.players
+                );
+                dump.lines().map(str::to_string).collect()
+            },
+            DebugCommand::Help => DEBUG_HELP.iter().map(|line| line.to_string()).collect(),
+            //the app handles these itself
+            DebugCommand::Debug(_) | DebugCommand::Clear => Vec::new(),
+        }
+    }
+}
+
+/// players are numbered from 1, or named
+fn parse_player(text: &str, data: &Data) -> Result<usize, String> {
+    if let Ok(number) = text.parse::<usize>() {
+        if (1..=data.players.len()).contains(&number) {
+            return Ok(number - 1);
+        }
+        return Err(format!("there is no player {}", number));
+    }
+    data.players
+        .iter()
+        .position(|player| player.name().eq_ignore_ascii_case(text))
+        .ok_or_else(|| format!("there is no player `{}`", text))
+}
@@ -0,0 +1,125 @@
+//endless.rs
+//"double or nothing": after a won match the player can keep going for a bigger multiplier,
+//but dying at any stage loses the whole run
+use std::fs;
+
+use crate::components::enums::ReloadAmount;
+use crate::config::config_dir;
+
+const HIGH_SCORE_FILE: &str = "highscores";
+const MAX_HIGH_SCORES: usize = 10;
+
+/// points for winning a stage before the multiplier
+const STAGE_POINTS: u64 = 100;
+/// extra points for every health left at the end of a stage
+const HEALTH_POINTS: u64 = 25;
+/// added to every exotic shell weight per stage after the first
+const EXOTIC_BONUS_PER_STAGE: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct EndlessRun {
+    /// starts at 1
+    pub stage: usize,
+    pub score: u64,
+    pub multiplier: u64,
+}
+
+impl EndlessRun {
+    pub fn new() -> Self {
+        EndlessRun {
+            stage: 1,
+            score: 0,
+            multiplier: 1,
+        }
+    }
+
+    /// shells loaded per reload, growing with the stage
+    pub fn reload_amount(&self) -> ReloadAmount {
+        ReloadAmount::for_round(self.stage)
+    }
+
+    pub fn exotic_bonus(&self) -> usize {
+        (self.stage - 1) * EXOTIC_BONUS_PER_STAGE
+    }
+
+    /// adds the points for the stage just won and returns them
+    pub fn bank_win(&mut self, health_left: u8) -> u64 {
+        let earned = (STAGE_POINTS + HEALTH_POINTS * health_left as u64) * self.multiplier;
+        self.score += earned;
+        earned
+    }
+
+    /// double or nothing, move on to a harder stage
+    pub fn advance(&mut self) {
+        self.stage += 1;
+        self.multiplier *= 2;
+    }
+}
+
+impl Default for EndlessRun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HighScore {
+    pub score: u64,
+    pub stage: usize,
+}
+
+/// Local high-score table, stored as one `score stage` pair per line.
+#[derive(Debug, Default)]
+pub struct HighScores {
+    pub entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// reads the table from the config directory, a missing or broken file is an empty table
+    pub fn load() -> Self {
+        let Some(path) = config_dir().map(|dir| dir.join(HIGH_SCORE_FILE)) else {
+            return HighScores::default();
+        };
+        let entries = fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let score = parts.next()?.parse().ok()?;
+                let stage = parts.next()?.parse().ok()?;
+                Some(HighScore { score, stage })
+            })
+            .collect();
+
+        let mut high_scores = HighScores { entries };
+        high_scores.sort_and_trim();
+        high_scores
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let dir = config_dir().ok_or_else(|| std::io::Error::other("no config directory"))?;
+        fs::create_dir_all(&dir)?;
+        let contents: String = self.entries
+            .iter()
+            .map(|entry| format!("{} {}\n", entry.score, entry.stage))
+            .collect();
+        fs::write(dir.join(HIGH_SCORE_FILE), contents)
+    }
+
+    /// adds a finished run, returns its 1-based rank if it made the table
+    pub fn record(&mut self, score: u64, stage: usize) -> Option<usize> {
+        //a tie goes below the runs that got there first
+        let idx = self.entries.partition_point(|entry| entry.score >= score);
+        if idx >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.entries.insert(idx, HighScore { score, stage });
+        self.entries.truncate(MAX_HIGH_SCORES);
+        Some(idx + 1)
+    }
+
+    fn sort_and_trim(&mut self) {
+        self.entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        self.entries.truncate(MAX_HIGH_SCORES);
+    }
+}
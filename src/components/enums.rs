@@ -1,12 +1,6 @@
-#[derive(Default, Debug)]
-pub enum Menu {
-    #[default]
-    Off,
-    Log,
-    Help,
-}
+use std::fmt::Debug;
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum ReloadAmount {
     #[default]
     One = 3,
@@ -17,7 +11,45 @@ pub enum ReloadAmount {
 }
 
 impl ReloadAmount {
+    pub const ALL: [ReloadAmount; 5] = [
+        ReloadAmount::One,
+        ReloadAmount::Two,
+        ReloadAmount::Three,
+        ReloadAmount::Four,
+        ReloadAmount::Five,
+    ];
+
+    /// the built-in shell count, the rules file can override it
     pub fn as_usize(&self) -> usize {
-        self.clone() as usize
+        *self as usize
+    }
+
+    /// position in `ALL`
+    pub fn index(&self) -> usize {
+        ReloadAmount::ALL.iter().position(|amount| amount == self).unwrap_or(0)
     }
+
+    /// the reload size used for a given round, capped at `Five`
+    pub fn for_round(round: usize) -> ReloadAmount {
+        match round {
+            1 => ReloadAmount::One,
+            2 => ReloadAmount::Two,
+            3 => ReloadAmount::Three,
+            4 => ReloadAmount::Four,
+            _ => ReloadAmount::Five,
+        }
+    }
+}
+
+/// finds the variant in `all` whose name matches, ignoring case, `_`, `-` and spaces,
+/// so `magnifying_glass` and `MagnifyingGlass` are the same thing
+pub fn parse_variant<T: Debug + Clone>(name: &str, all: &[T]) -> Option<T> {
+    let normalize = |text: &str| -> String {
+        text.chars()
+            .filter(|c| !matches!(c, '_' | '-' | ' '))
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    let wanted = normalize(name);
+    all.iter().find(|variant| normalize(&format!("{:?}", variant)) == wanted).cloned()
 }
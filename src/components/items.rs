@@ -1,9 +1,10 @@
 //items.rs
-use rand::thread_rng;
+use serde::{Deserialize, Serialize};
 
 //local and online multiplayer, and singleplayer
 //multiplayer items easier to pull off, ten second timer is when a shot could be first fired
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Items {
     Saw, //doubles damage
     Beer, //ejects the next shell
@@ -13,7 +14,92 @@ pub enum Items {
     MagnifyingGlass, //shows current shell
     Handcuffs, //skips next player's turn
     Meth, //restores two health, but keeps you from being able to see the next shell
+    #[serde(rename = "aed")]
     AED, //secret and default, if shot next round it keeps the health, if not lose a health
     Adrenaline,
+    #[serde(rename = "lsd")]
     LSD, //shows a shell in the future, specifies which one
 }
+
+/// how rare an item is, for showing it off when it is dealt.
+/// How often it actually comes up is set by the rules' `item_pool`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl Items {
+    pub const ALL: [Items; 11] = [
+        Items::Saw,
+        Items::Beer,
+        Items::Cigarette,
+        Items::Mirror,
+        Items::Inverter,
+        Items::MagnifyingGlass,
+        Items::Handcuffs,
+        Items::Meth,
+        Items::AED,
+        Items::Adrenaline,
+        Items::LSD,
+    ];
+
+    pub fn rarity(&self) -> Rarity {
+        match self {
+            Items::Saw | Items::Beer | Items::Cigarette | Items::MagnifyingGlass => Rarity::Common,
+            Items::Handcuffs | Items::Inverter => Rarity::Uncommon,
+            Items::Mirror | Items::Meth | Items::AED | Items::Adrenaline | Items::LSD => Rarity::Rare,
+        }
+    }
+
+    /// a few characters drawn in the inventory slot
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            Items::Saw => "=/=",
+            Items::Beer => "(U)",
+            Items::Cigarette => "==~",
+            Items::Mirror => "[|]",
+            Items::Inverter => "+/-",
+            Items::MagnifyingGlass => "(o-",
+            Items::Handcuffs => "o-o",
+            Items::Meth => "<*>",
+            Items::AED => "~^~",
+            Items::Adrenaline => "-|>",
+            Items::LSD => "@@@",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Items::Saw => "Saw",
+            Items::Beer => "Beer",
+            Items::Cigarette => "Cigarette",
+            Items::Mirror => "Mirror",
+            Items::Inverter => "Inverter",
+            Items::MagnifyingGlass => "Magnifying Glass",
+            Items::Handcuffs => "Handcuffs",
+            Items::Meth => "Meth",
+            Items::AED => "AED",
+            Items::Adrenaline => "Adrenaline",
+            Items::LSD => "LSD",
+        }
+    }
+
+    /// what the item does, in a sentence
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Items::Saw => "Saw: the next shot does double damage.",
+            Items::Beer => "Beer: ejects the next shell without firing it.",
+            Items::Cigarette => "Cigarette: restores one health.",
+            Items::Mirror => "Mirror: deflects the next shot back at the shooter.",
+            Items::Inverter => "Inverter: flips the next shell, a Live becomes a Blank and a Blank becomes a Live.",
+            Items::MagnifyingGlass => "Magnifying Glass: shows you the next shell.",
+            Items::Handcuffs => "Handcuffs: the other player skips their next turn.",
+            Items::Meth => "Meth: restores two health, but you can't look at the next shell.",
+            Items::AED => "AED: if you are shot next round you keep your health, if not you lose one.",
+            Items::Adrenaline => "Adrenaline: steal an item from the other player and use it right away.",
+            Items::LSD => "LSD: shows one shell further down the shotgun, and which one it is.",
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//knowledge.rs
+//what a player believes is in the shotgun, which is not always what is really there
+use std::collections::BTreeMap;
+
+use crate::components::shotgun::{Shell, Shotgun};
+
+/// shells a player has seen in the current load. Positions count from the bottom of the
+/// chamber, so they stay put as shells are fired off the top.
+#[derive(Debug, Default, Clone)]
+pub struct Knowledge {
+    /// the `Shotgun::load_id` this knowledge belongs to
+    load: u32,
+    seen: BTreeMap<usize, Shell>,
+}
+
+impl Knowledge {
+    pub fn new() -> Self {
+        Knowledge::default()
+    }
+
+    /// remembers what the shell `depth` shots away looked like, anything from an older load is forgotten
+    pub fn record(&mut self, shotgun: &Shotgun, depth: usize, seen: Shell) {
+        let load = shotgun.load_id.get();
+        if self.load != load {
+            self.seen.clear();
+            self.load = load;
+        }
+        let len = shotgun.shells.borrow().len();
+        if depth < len {
+            self.seen.insert(len - 1 - depth, seen);
+        }
+    }
+
+    pub fn forget(&mut self, shotgun: &Shotgun, depth: usize) {
+        let len = shotgun.shells.borrow().len();
+        if depth < len {
+            self.seen.remove(&(len - 1 - depth));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+    }
+
+    /// believed shell at `depth` shots away, 0 being the next one
+    pub fn at(&self, shotgun: &Shotgun, depth: usize) -> Option<&Shell> {
+        self.known(shotgun)
+            .into_iter()
+            .find(|(known_depth, _)| *known_depth == depth)
+            .map(|(_, shell)| shell)
+    }
+
+    /// every shell still in the chamber that the player believes they know, nearest first
+    pub fn known(&self, shotgun: &Shotgun) -> Vec<(usize, &Shell)> {
+        if self.load != shotgun.load_id.get() {
+            return Vec::new();
+        }
+        let len = shotgun.shells.borrow().len();
+        self.seen
+            .iter()
+            .rev()
+            .filter(|(position, _)| **position < len)
+            .map(|(position, shell)| (len - 1 - position, shell))
+            .collect()
+    }
+}
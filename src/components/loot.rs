@@ -0,0 +1,61 @@
+//loot.rs
+//items handed out after every reload, drawn from the rules' item pool
+use std::collections::BTreeMap;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+
+use crate::components::items::Items;
+
+/// ticks between two items being laid on the table
+pub const TICKS_PER_ITEM: usize = 6;
+/// ticks the finished layout stays up before it is cleared
+pub const LINGER_TICKS: usize = 45;
+
+/// what one player got from a deal
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootDeal {
+    pub player: usize,
+    pub items: Vec<Items>,
+    /// some items were held back because the inventory was full
+    pub capped: bool,
+}
+
+/// draws `count` items, weighted by the pool, an empty pool draws nothing
+pub fn roll(rng: &mut impl Rng, pool: &BTreeMap<Items, usize>, count: usize) -> Vec<Items> {
+    let (items, weights): (Vec<Items>, Vec<usize>) = pool.iter().map(|(item, weight)| (*item, *weight)).unzip();
+    let Ok(dist) = WeightedIndex::new(&weights) else {
+        return Vec::new();
+    };
+    (0..count).map(|_| items[dist.sample(rng)]).collect()
+}
+
+/// the items of a deal being laid out one at a time, in the order they were dealt
+#[derive(Debug, Clone)]
+pub struct LootAnimation {
+    pub deals: Vec<LootDeal>,
+    ticks: usize,
+}
+
+impl LootAnimation {
+    pub fn new(deals: Vec<LootDeal>) -> Self {
+        LootAnimation { deals, ticks: 0 }
+    }
+
+    pub fn tick(&mut self) {
+        self.ticks += 1;
+    }
+
+    fn total(&self) -> usize {
+        self.deals.iter().map(|deal| deal.items.len()).sum()
+    }
+
+    /// how many items are on the table so far, counted across every deal
+    pub fn shown(&self) -> usize {
+        (self.ticks / TICKS_PER_ITEM).min(self.total())
+    }
+
+    pub fn done(&self) -> bool {
+        self.ticks >= self.total() * TICKS_PER_ITEM + LINGER_TICKS
+    }
+}
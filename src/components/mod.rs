@@ -3,3 +3,14 @@ pub mod shotgun;
 pub mod player;
 pub mod enums;
 pub mod match_data;
+pub mod dealer;
+pub mod endless;
+pub mod daily;
+pub mod tutorial;
+pub mod sandbox;
+pub mod debug_commands;
+pub mod loot;
+pub mod status;
+pub mod knowledge;
+pub mod odds;
+pub mod commands;
@@ -0,0 +1,85 @@
+//odds.rs
+//chances for the next shell, worked out only from what a player could know
+use std::collections::BTreeMap;
+
+use crate::components::knowledge::Knowledge;
+use crate::components::shotgun::{Shell, Shotgun};
+
+/// what everyone can tell is still in the chamber: the announced load minus the spent shells.
+/// An Imposter is counted as the Blank it was announced as, an inverted shell as what it was before.
+pub fn remaining(shotgun: &Shotgun) -> BTreeMap<Shell, usize> {
+    let mut left: BTreeMap<Shell, usize> = BTreeMap::new();
+    for shell in shotgun.announced.borrow().iter() {
+        *left.entry(shell.clone()).or_default() += 1;
+    }
+    for spent in shotgun.spent.borrow().iter() {
+        if let Some(count) = left.get_mut(&spent.counted_as) {
+            *count = count.saturating_sub(1);
+        }
+    }
+    left.retain(|_, count| *count > 0);
+    left
+}
+
+/// chance of each kind of shell coming next, most likely first.
+///
+/// Starts from what is `remaining` and takes away what this player has seen further down.
+pub fn next_shell_odds(shotgun: &Shotgun, knowledge: &Knowledge) -> Vec<(Shell, f64)> {
+    let known = knowledge.known(shotgun);
+    if let Some((_, shell)) = known.iter().find(|(depth, _)| *depth == 0) {
+        return vec![((*shell).clone(), 1.0)];
+    }
+
+    let mut left = remaining(shotgun);
+    for (_, shell) in known {
+        if let Some(count) = left.get_mut(shell) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    let total: usize = left.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+    let mut odds: Vec<(Shell, f64)> = left
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(shell, count)| (shell, count as f64 / total as f64))
+        .collect();
+    odds.sort_by(|a, b| b.1.total_cmp(&a.1));
+    odds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_imposter_counts_as_a_blank() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Imposter, Shell::Live, Shell::Blank]);
+        shotgun.shoot(0, Some(1));
+        assert_eq!(remaining(&shotgun), BTreeMap::from([(Shell::Live, 1), (Shell::Blank, 1)]));
+    }
+
+    #[test]
+    fn an_inverted_shell_counts_as_what_was_announced() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Live, Shell::Blank]);
+        shotgun.invert();
+        assert_eq!(shotgun.shoot(0, Some(1)), Some(Shell::Blank));
+        //the Live was used up, only the announced Blank is left
+        assert_eq!(remaining(&shotgun), BTreeMap::from([(Shell::Blank, 1)]));
+        assert_eq!(next_shell_odds(&shotgun, &Knowledge::new()), vec![(Shell::Blank, 1.0)]);
+    }
+
+    #[test]
+    fn inverting_twice_changes_nothing() {
+        let shotgun = Shotgun::new();
+        shotgun.load_exact(vec![Shell::Blank, Shell::Live]);
+        shotgun.invert();
+        shotgun.invert();
+        assert_eq!(shotgun.shoot(0, None), Some(Shell::Blank));
+        assert_eq!(remaining(&shotgun), BTreeMap::from([(Shell::Live, 1)]));
+    }
+}
@@ -1,9 +1,165 @@
 use crate::components::items::Items;
+use crate::components::knowledge::Knowledge;
+use crate::components::status::{Expiry, Stacking, StatusEffect, StatusKind, TurnTick};
 
 #[derive(Debug, Clone)]
 pub struct Player {
     name: String,
     id: u8,
     health: u8,
-    items: Vec<Items>
+    items: Vec<Items>,
+    statuses: Vec<StatusEffect>,
+    knowledge: Knowledge,
+}
+
+impl Player {
+    pub fn new(name: &str, id: u8, health: u8) -> Self {
+        Player {
+            name: name.to_string(),
+            id,
+            health,
+            items: Vec::new(),
+            statuses: Vec::new(),
+            knowledge: Knowledge::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    pub fn health(&self) -> u8 {
+        self.health
+    }
+
+    pub fn items(&self) -> &[Items] {
+        &self.items
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health == 0
+    }
+
+    pub fn set_health(&mut self, health: u8) {
+        self.health = health;
+    }
+
+    /// adds `item` unless the inventory already holds `max` items
+    pub fn give_item(&mut self, item: Items, max: usize) -> Result<(), String> {
+        if self.items.len() >= max {
+            return Err(format!("{} can't carry more than {} items", self.name, max));
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    /// removes and returns the item in `slot`
+    pub fn take_item(&mut self, slot: usize) -> Option<Items> {
+        (slot < self.items.len()).then(|| self.items.remove(slot))
+    }
+
+    pub fn clear_items(&mut self) {
+        self.items.clear();
+    }
+
+    /// restores health, never past `max`
+    pub fn heal(&mut self, amount: u8, max: u8) {
+        self.health = self.health.saturating_add(amount).min(max.max(self.health));
+    }
+
+    pub fn damage(&mut self, amount: u8) {
+        self.health = self.health.saturating_sub(amount);
+    }
+
+    pub fn knowledge(&self) -> &Knowledge {
+        &self.knowledge
+    }
+
+    pub fn knowledge_mut(&mut self) -> &mut Knowledge {
+        &mut self.knowledge
+    }
+
+    pub fn statuses(&self) -> &[StatusEffect] {
+        &self.statuses
+    }
+
+    pub fn has_status(&self, kind: StatusKind) -> bool {
+        self.statuses.iter().any(|status| status.kind == kind)
+    }
+
+    /// applies an effect, following its stacking rule if the player already has it
+    pub fn add_status(&mut self, kind: StatusKind) {
+        let fresh = StatusEffect::new(kind);
+        match self.statuses.iter_mut().find(|status| status.kind == kind) {
+            Some(status) => {
+                status.turns = fresh.turns;
+                if kind.stacking() == Stacking::Intensify {
+                    status.stacks = status.stacks.saturating_add(1);
+                }
+            },
+            None => self.statuses.push(fresh),
+        }
+    }
+
+    /// removes an effect, returning whether the player had it
+    pub fn take_status(&mut self, kind: StatusKind) -> bool {
+        let had = self.has_status(kind);
+        self.statuses.retain(|status| status.kind != kind);
+        had
+    }
+
+    pub fn clear_statuses(&mut self) {
+        self.statuses.clear();
+    }
+
+    /// runs the player's effects as their turn starts, counting down and expiring them
+    pub fn tick_statuses(&mut self) -> TurnTick {
+        let mut tick = TurnTick::default();
+        for status in self.statuses.iter_mut() {
+            if status.kind == StatusKind::Poisoned {
+                tick.damage = tick.damage.saturating_add(status.stacks);
+            }
+            if status.kind.skips_turn() && tick.skipped.is_none() {
+                tick.skipped = Some(status.kind);
+            }
+            if let Expiry::Turns(_) = status.kind.expiry() {
+                status.turns = status.turns.saturating_sub(1);
+                if status.turns == 0 {
+                    tick.expired.push(status.kind);
+                }
+            }
+        }
+        self.statuses.retain(|status| !tick.expired.contains(&status.kind));
+        self.damage(tick.damage);
+        tick
+    }
+
+    /// expires the effects that only last for the round, returning which ones ran out
+    pub fn end_round_statuses(&mut self) -> Vec<StatusKind> {
+        let expired: Vec<StatusKind> = self.statuses
+            .iter()
+            .filter(|status| status.kind.expiry() == Expiry::Round)
+            .map(|status| status.kind)
+            .collect();
+        self.statuses.retain(|status| !expired.contains(&status.kind));
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn give_item_stops_at_a_full_inventory() {
+        let mut player = Player::new("You", 0, 3);
+        assert!(player.give_item(Items::Saw, 2).is_ok());
+        assert!(player.give_item(Items::Beer, 2).is_ok());
+        assert_eq!(player.give_item(Items::Cigarette, 2), Err("You can't carry more than 2 items".to_string()));
+        assert_eq!(player.items(), [Items::Saw, Items::Beer]);
+    }
 }
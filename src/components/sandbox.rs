@@ -0,0 +1,181 @@
+//sandbox.rs
+//practice table where everything is set by hand instead of rolled, for reproducing situations
+use crate::components::items::Items;
+use crate::components::shotgun::{Shell, ShotgunModel, ShotgunState};
+use crate::data::Data;
+
+/// keys the sandbox panel reacts to
+#[derive(Debug, Clone, Copy)]
+pub enum SandboxInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// enter, run the selected row
+    Apply,
+    /// backspace, drop the last queued shell
+    Remove,
+}
+
+/// rows of the sandbox panel, top to bottom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SandboxRow {
+    Shell,
+    Load,
+    State,
+    Model,
+    Player,
+    Health,
+    Item,
+    Turn,
+}
+
+impl SandboxRow {
+    pub const ALL: [SandboxRow; 8] = [
+        SandboxRow::Shell,
+        SandboxRow::Load,
+        SandboxRow::State,
+        SandboxRow::Model,
+        SandboxRow::Player,
+        SandboxRow::Health,
+        SandboxRow::Item,
+        SandboxRow::Turn,
+    ];
+}
+
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    /// shells queued for the next load, first one is fired first
+    pub queued: Vec<Shell>,
+    pub row: usize,
+    /// index into `Shell::ALL`
+    pub shell: usize,
+    /// index into `Items::ALL`
+    pub item: usize,
+    /// the player that health and items are applied to
+    pub player: usize,
+}
+
+impl Sandbox {
+    pub fn new() -> Self {
+        Sandbox {
+            queued: Vec::new(),
+            row: 0,
+            shell: 0,
+            item: 0,
+            player: 0,
+        }
+    }
+
+    pub fn selected_row(&self) -> SandboxRow {
+        SandboxRow::ALL[self.row]
+    }
+
+    pub fn selected_shell(&self) -> &Shell {
+        &Shell::ALL[self.shell]
+    }
+
+    pub fn selected_item(&self) -> Items {
+        Items::ALL[self.item]
+    }
+
+    /// applies a key to the panel and the table, returns a line for the log if something changed
+    pub fn handle(&mut self, input: SandboxInput, data: &mut Data) -> Option<String> {
+        let rows = SandboxRow::ALL.len();
+        match input {
+            SandboxInput::Up => {
+                self.row = (self.row + rows - 1) % rows;
+                None
+            },
+            SandboxInput::Down => {
+                self.row = (self.row + 1) % rows;
+                None
+            },
+            SandboxInput::Left => self.cycle(data, false),
+            SandboxInput::Right => self.cycle(data, true),
+            SandboxInput::Remove => {
+                self.queued.pop().map(|shell| format!("Unqueued {:?}", shell))
+            },
+            SandboxInput::Apply => self.apply(data),
+        }
+    }
+
+    fn cycle(&mut self, data: &mut Data, forward: bool) -> Option<String> {
+        let step = |idx: usize, len: usize| if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+        match self.selected_row() {
+            SandboxRow::Shell => {
+                self.shell = step(self.shell, Shell::ALL.len());
+                None
+            },
+            SandboxRow::Item => {
+                self.item = step(self.item, Items::ALL.len());
+                None
+            },
+            SandboxRow::Player => {
+                self.player = step(self.player, data.players.len());
+                None
+            },
+            SandboxRow::State => {
+                let idx = ShotgunState::ALL.iter().position(|state| *state == data.shotgun.state).unwrap_or(0);
+                data.shotgun.state = ShotgunState::ALL[step(idx, ShotgunState::ALL.len())];
+                Some(format!("Shotgun state set to {:?}", data.shotgun.state))
+            },
+            SandboxRow::Model => {
+                let idx = ShotgunModel::ALL.iter().position(|model| *model == data.shotgun.model).unwrap_or(0);
+                data.shotgun.model = ShotgunModel::ALL[step(idx, ShotgunModel::ALL.len())];
+                Some(format!("Shotgun model set to {:?}", data.shotgun.model))
+            },
+            SandboxRow::Health => {
+                //nobody dies from the panel, that would end the match without a shot
+                let max = data.rules.starting_health.max(1);
+                let player = &mut data.players[self.player];
+                let health = if forward {
+                    (player.health() + 1).min(max)
+                } else {
+                    player.health().saturating_sub(1).max(1)
+                };
+                player.set_health(health);
+                Some(format!("{} health set to {}", player.name(), health))
+            },
+            SandboxRow::Turn => {
+                let turn = data.match_data.turn.map_or(0, |turn| step(turn, data.players.len()));
+                data.match_data.turn = Some(turn);
+                Some(format!("It is now {}'s turn", data.players[turn].name()))
+            },
+            SandboxRow::Load => None,
+        }
+    }
+
+    fn apply(&mut self, data: &mut Data) -> Option<String> {
+        match self.selected_row() {
+            SandboxRow::Shell => {
+                self.queued.push(self.selected_shell().clone());
+                Some(format!("Queued {:?}", self.selected_shell()))
+            },
+            SandboxRow::Load => {
+                if self.queued.is_empty() {
+                    return Some("Queue some shells before loading".to_string());
+                }
+                let shells = std::mem::take(&mut self.queued);
+                let msg = format!("Loaded {:?}", shells);
+                data.shotgun.load_exact(shells);
+                Some(msg)
+            },
+            SandboxRow::Item => {
+                let item = self.selected_item();
+                let player = &mut data.players[self.player];
+                match player.give_item(item, data.rules.inventory_size) {
+                    Ok(()) => Some(format!("Gave {:?} to {}", item, player.name())),
+                    Err(msg) => Some(msg),
+                }
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
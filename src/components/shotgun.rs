@@ -1,22 +1,76 @@
 //shotgun.rs
-use rand::{ Rng, seq::SliceRandom, thread_rng, distributions::{WeightedIndex, Distribution} };
-use std::cell::RefCell;
+use rand::{ Rng, distributions::{WeightedIndex, WeightedError, Distribution} };
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Why the shotgun could not be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShotgunError {
+    /// every shell needs exactly one weight
+    MismatchedWeights { shells: usize, weights: usize },
+    /// the weights can't be drawn from, e.g. they are all zero
+    InvalidWeights(WeightedError),
+}
+
+impl fmt::Display for ShotgunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShotgunError::MismatchedWeights { shells, weights } => {
+                write!(f, "{} shells were given {} weights", shells, weights)
+            },
+            ShotgunError::InvalidWeights(err) => write!(f, "invalid shell weights: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ShotgunError {}
+
+impl From<WeightedError> for ShotgunError {
+    fn from(err: WeightedError) -> Self {
+        ShotgunError::InvalidWeights(err)
+    }
+}
 
 #[derive(Debug, Default, Clone)]
 pub struct Shotgun {
     pub shells: RefCell<Vec<Shell>>,
     pub state: ShotgunState,
     pub model: ShotgunModel,
+    /// goes up every time new shells go in, so old knowledge of the chamber can be told apart
+    pub load_id: Cell<u32>,
+    /// the load as it was announced, how the shells look rather than what they are
+    pub announced: RefCell<Vec<Shell>>,
+    /// shells fired or ejected since the last load, oldest first
+    pub spent: RefCell<Vec<SpentShell>>,
+    /// what the next shell was before an Inverter flipped it
+    inverted_from: RefCell<Option<Shell>>,
 }
 
-#[derive(Debug, Default, Clone)]
+/// a shell that has left the chamber, and who sent it where
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpentShell {
+    pub shell: Shell,
+    /// what it takes off the announced load, an inverted shell counts as what it was announced as
+    pub counted_as: Shell,
+    /// index into `Data.players` of whoever fired or ejected it
+    pub by: usize,
+    /// who it was fired at, `None` for an ejected shell
+    pub at: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShotgunModel {
     #[default]
     Default,
     Revolver, //does twice the amount of damage
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ShotgunState {
     #[default]
     Default,
@@ -26,7 +80,8 @@ pub enum ShotgunState {
     Reinforced, //Destruct shell becomes offensive but also destroys the shotgun
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Shell {
     Live,
     #[default]
@@ -38,6 +93,77 @@ pub enum Shell {
     SelfDestruct, //blows up in the person's face if not reinforced
 }
 
+impl ShotgunModel {
+    pub const ALL: [ShotgunModel; 2] = [ShotgunModel::Default, ShotgunModel::Revolver];
+
+    /// what the model changes, in a sentence
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ShotgunModel::Default => "Default: a harmful shell deals one damage.",
+            ShotgunModel::Revolver => "Revolver: every harmful shell deals double damage.",
+        }
+    }
+}
+
+impl ShotgunState {
+    pub const ALL: [ShotgunState; 5] = [
+        ShotgunState::Default,
+        ShotgunState::SawedOff,
+        ShotgunState::Rusty,
+        ShotgunState::ThickBarrel,
+        ShotgunState::Reinforced,
+    ];
+
+    /// what the state changes, in a sentence
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ShotgunState::Default => "Default: nothing out of the ordinary.",
+            ShotgunState::SawedOff => "SawedOff: the next shot does double damage, then the barrel is back to normal.",
+            ShotgunState::Rusty => "Rusty: a worn barrel, it shoots like the default one.",
+            ShotgunState::ThickBarrel => "ThickBarrel: too thick for the Saw, it can't be sawed off.",
+            ShotgunState::Reinforced => "Reinforced: a SelfDestruct shell hits the target instead of the shooter.",
+        }
+    }
+}
+
+impl Shell {
+    pub const ALL: [Shell; 7] = [
+        Shell::Live,
+        Shell::Blank,
+        Shell::Poison,
+        Shell::BeanBag,
+        Shell::Taser,
+        Shell::Imposter,
+        Shell::SelfDestruct,
+    ];
+
+    /// anything that isn't a plain Live or Blank
+    pub fn is_exotic(&self) -> bool {
+        !matches!(self, Shell::Live | Shell::Blank)
+    }
+
+    /// what the shell looks like to whoever inspects it, the Imposter passes for a Blank
+    pub fn apparent(&self) -> Shell {
+        match self {
+            Shell::Imposter => Shell::Blank,
+            other => other.clone(),
+        }
+    }
+
+    /// what the shell does, in a sentence
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Shell::Live => "Live: deals damage to whoever it is fired at.",
+            Shell::Blank => "Blank: does nothing. Fire one at yourself and you keep your turn.",
+            Shell::Poison => "Poison: no damage right away, but the target loses health at the start of their next two turns.",
+            Shell::BeanBag => "BeanBag: no damage, but stuns the target so they skip their next turn.",
+            Shell::Taser => "Taser: deals damage to whoever it is fired at.",
+            Shell::Imposter => "Imposter: looks exactly like a Blank, but hurts like a Live.",
+            Shell::SelfDestruct => "SelfDestruct: blows up in the shooter's face, unless the shotgun is Reinforced.",
+        }
+    }
+}
+
 //BeanBag round limits the player to only use one item
 //Russian Roulette item, play russian roulette for a turn instead of the shotgun
 
@@ -50,6 +176,10 @@ impl Shotgun {
             shells: RefCell::new(Vec::new()),
             state: ShotgunState::Default,
             model: ShotgunModel::Default,
+            load_id: Cell::new(0),
+            announced: RefCell::new(Vec::new()),
+            spent: RefCell::new(Vec::new()),
+            inverted_from: RefCell::new(None),
         }
     }
 
@@ -57,70 +187,151 @@ impl Shotgun {
         self.shells.borrow().is_empty()
     }
 
-    pub fn load(&self,all_shells: Vec<Shell>, weights: Vec<usize>, num_shells: usize) {
-        let mut rng = thread_rng();
+    /// loads `num_shells` drawn from `all_shells`, `weights[i]` being the weight of `all_shells[i]`.
+    /// On an error the shotgun is left as it was.
+    pub fn load(
+        &self,
+        rng: &mut impl Rng,
+        all_shells: Vec<Shell>,
+        weights: Vec<usize>,
+        num_shells: usize,
+    ) -> Result<(), ShotgunError> {
+        if all_shells.len() != weights.len() {
+            return Err(ShotgunError::MismatchedWeights { shells: all_shells.len(), weights: weights.len() });
+        }
+        let dist = WeightedIndex::new(&weights)?;
+        //rules that weigh Blank at 0 asked for no blanks at all
+        let blanks_allowed = all_shells.iter()
+            .zip(&weights)
+            .any(|(shell, weight)| *shell == Shell::Blank && *weight > 0);
+
         let mut shells = self.shells.borrow_mut();
         shells.clear();
 
-        let dist = WeightedIndex::new(&weights)
-            .expect("weights can not be zero or negative");
-
         for _ in 0..num_shells {
-            let idx = dist.sample(&mut rng);
+            let idx = dist.sample(rng);
             let random_shell = all_shells[idx].clone();
             shells.push(random_shell);
         }
 
-        if !shells.is_empty(){
-            if !shells.contains(&Shell::Blank) {
-                let num: usize = rng.gen_range(0..shells.len());
-                shells[num] = Shell::Blank;
-            }
+        if blanks_allowed && !shells.is_empty() && !shells.contains(&Shell::Blank) {
+            let num: usize = rng.gen_range(0..shells.len());
+            shells[num] = Shell::Blank;
         }
+        drop(shells);
+        self.announce();
+        Ok(())
+    }
+
+    /// loads exactly `shells`, the first one in the list is fired first
+    pub fn load_exact(&self, shells: Vec<Shell>) {
+        let mut loaded = self.shells.borrow_mut();
+        *loaded = shells;
+        loaded.reverse();
+        drop(loaded);
+        self.announce();
+    }
+
+    /// loads `num_shells` drawn from the weights in the rules' `random_shells`
+    pub fn load_random_shells(
+        &self,
+        rng: &mut impl Rng,
+        weights: &BTreeMap<Shell, usize>,
+        num_shells: usize,
+    ) -> Result<(), ShotgunError> {
+        self.load_escalated_shells(rng, weights, num_shells, 0)
+    }
+
+    /// same as `load_random_shells`, but every exotic shell weight is raised by `exotic_bonus`
+    pub fn load_escalated_shells(
+        &self,
+        rng: &mut impl Rng,
+        weights: &BTreeMap<Shell, usize>,
+        num_shells: usize,
+        exotic_bonus: usize,
+    ) -> Result<(), ShotgunError> {
+        //an exotic shell left out of the table stays out, the bonus only raises what is there
+        let (all_shells, weights) = weights.iter()
+            .map(|(shell, weight)| {
+                let weight = if shell.is_exotic() && *weight > 0 { weight + exotic_bonus } else { *weight };
+                (shell.clone(), weight)
+            })
+            .unzip();
+
+        self.load(rng, all_shells, weights, num_shells)
     }
 
-    pub fn load_random_shells(&self, num_shells: usize) {
-        let all_shells = vec![
-            Shell::Live,
-            Shell::Blank,
-            Shell::Poison,
-            Shell::BeanBag,
-            Shell::Taser,
-            Shell::Imposter,
-        ];
-
-        let weights = vec![
-            10, //Live
-            12, //Blank
-            1, //Poison
-            2, //BeanBag
-            1, //Taser
-            1, //Imposter
-        ];
-
-
-        self.load(all_shells, weights, num_shells);
-    }
-
-    pub fn load_default_shells(&self, num_shells: usize) {
-        let all_shells = vec![
-            Shell::Live,
-            Shell::Blank,
-        ];
-
-        let weights = vec![
-            10, //Live
-            14, //Blank
-        ];
-        self.load(all_shells, weights, num_shells);
-    }
-
-    pub fn shoot(&self) -> Option<String>{
-        let mut shell_borrow = self.shells.borrow_mut();
-        if let Some(popped_shell) = shell_borrow.pop() {
-            Some(format!("Popped shell: {:?}", popped_shell))
-        } else {
-            Some("No shell in shotgun.".to_string())
+    /// loads from the rules' `default_shells`, the classic Live and Blank only game
+    pub fn load_default_shells(
+        &self,
+        rng: &mut impl Rng,
+        weights: &BTreeMap<Shell, usize>,
+        num_shells: usize,
+    ) -> Result<(), ShotgunError> {
+        let (all_shells, weights) = weights.iter()
+            .map(|(shell, weight)| (shell.clone(), *weight))
+            .unzip();
+        self.load(rng, all_shells, weights, num_shells)
+    }
+
+    /// takes the next shell out, `at` is `None` when it is ejected rather than fired
+    pub fn shoot(&self, by: usize, at: Option<usize>) -> Option<Shell> {
+        let shell = self.shells.borrow_mut().pop();
+        let inverted_from = self.inverted_from.borrow_mut().take();
+        if let Some(shell) = &shell {
+            let counted_as = inverted_from.unwrap_or_else(|| shell.clone()).apparent();
+            self.spent.borrow_mut().push(SpentShell { shell: shell.clone(), counted_as, by, at });
+        }
+        shell
+    }
+
+    /// flips the next shell between Live and Blank, exotic shells don't have a polarity to flip
+    pub fn invert(&self) {
+        let mut shells = self.shells.borrow_mut();
+        let Some(shell) = shells.last_mut() else {
+            return;
+        };
+        let flipped = match shell {
+            Shell::Live => Shell::Blank,
+            Shell::Blank => Shell::Live,
+            _ => return,
+        };
+        //flipped twice it is back to what it was, and still counts as that
+        self.inverted_from.borrow_mut().get_or_insert_with(|| shell.clone());
+        *shell = flipped;
+    }
+
+    /// starts a new load: everyone is told what went in, and what they knew is stale
+    fn announce(&self) {
+        self.load_id.set(self.load_id.get() + 1);
+        let mut announced: Vec<Shell> = self.shells.borrow().iter().map(Shell::apparent).collect();
+        announced.sort();
+        *self.announced.borrow_mut() = announced;
+        self.spent.borrow_mut().clear();
+        self.inverted_from.borrow_mut().take();
+    }
+
+    /// the announced load counted by kind, e.g. "2 Live, 3 Blank"
+    pub fn announcement(&self) -> String {
+        let mut counts: BTreeMap<Shell, usize> = BTreeMap::new();
+        for shell in self.announced.borrow().iter() {
+            *counts.entry(shell.clone()).or_default() += 1;
+        }
+        counts.iter()
+            .map(|(shell, count)| format!("{} {:?}", count, shell))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// damage dealt by a harmful shell with the current model and state
+    pub fn damage(&self) -> u8 {
+        let mut damage = 1;
+        if matches!(self.model, ShotgunModel::Revolver) {
+            damage *= 2;
+        }
+        if matches!(self.state, ShotgunState::SawedOff) {
+            damage *= 2;
         }
+        damage
     }
 }
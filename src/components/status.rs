@@ -0,0 +1,130 @@
+//status.rs
+//timed effects that shells and items leave on a player
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatusKind {
+    /// hit by a BeanBag, skips their next turn
+    Stunned,
+    /// hit by a Poison shell, loses health at the start of their turns
+    Poisoned,
+    /// from the Handcuffs, skips their next turn
+    Cuffed,
+    /// from the Meth, can't look inside the shotgun
+    Blind,
+    /// from the AED, the next hit this round is shrugged off, no hit costs a health
+    Aed,
+    /// from the Mirror, the next shot at them this round goes back to the shooter
+    Mirrored,
+}
+
+/// when an effect runs out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Expiry {
+    /// after this many of the player's turns have started
+    Turns(u8),
+    /// when the shotgun is reloaded
+    Round,
+}
+
+/// what happens when an effect is applied to a player who already has it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stacking {
+    /// the duration starts over
+    Refresh,
+    /// the duration starts over and the effect gets stronger
+    Intensify,
+}
+
+impl StatusKind {
+    pub const ALL: [StatusKind; 6] = [
+        StatusKind::Stunned,
+        StatusKind::Poisoned,
+        StatusKind::Cuffed,
+        StatusKind::Blind,
+        StatusKind::Aed,
+        StatusKind::Mirrored,
+    ];
+
+    pub fn expiry(&self) -> Expiry {
+        match self {
+            StatusKind::Stunned | StatusKind::Cuffed | StatusKind::Blind => Expiry::Turns(1),
+            StatusKind::Poisoned => Expiry::Turns(2),
+            StatusKind::Aed | StatusKind::Mirrored => Expiry::Round,
+        }
+    }
+
+    pub fn stacking(&self) -> Stacking {
+        match self {
+            StatusKind::Poisoned => Stacking::Intensify,
+            _ => Stacking::Refresh,
+        }
+    }
+
+    /// skips the player's turn when it ticks
+    pub fn skips_turn(&self) -> bool {
+        matches!(self, StatusKind::Stunned | StatusKind::Cuffed)
+    }
+
+    /// reads after "is", like "the dealer is stunned"
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StatusKind::Stunned => "stunned",
+            StatusKind::Poisoned => "poisoned",
+            StatusKind::Cuffed => "cuffed",
+            StatusKind::Blind => "blind",
+            StatusKind::Aed => "hooked up to an AED",
+            StatusKind::Mirrored => "behind a Mirror",
+        }
+    }
+
+    /// short tag drawn beside the player
+    pub fn icon(&self) -> &'static str {
+        match self {
+            StatusKind::Stunned => "STN",
+            StatusKind::Poisoned => "PSN",
+            StatusKind::Cuffed => "CUF",
+            StatusKind::Blind => "BLD",
+            StatusKind::Aed => "AED",
+            StatusKind::Mirrored => "MIR",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    /// turns left for `Expiry::Turns` effects, unused for the others
+    pub turns: u8,
+    /// how strong the effect is, only `Stacking::Intensify` effects go above 1
+    pub stacks: u8,
+}
+
+impl StatusEffect {
+    pub fn new(kind: StatusKind) -> Self {
+        let turns = match kind.expiry() {
+            Expiry::Turns(turns) => turns,
+            Expiry::Round => 0,
+        };
+        StatusEffect { kind, turns, stacks: 1 }
+    }
+
+    /// e.g. `PSN x2 1t`, stacks and turns are left out when they say nothing
+    pub fn label(&self) -> String {
+        let mut label = self.kind.icon().to_string();
+        if self.stacks > 1 {
+            label.push_str(&format!(" x{}", self.stacks));
+        }
+        if let Expiry::Turns(_) = self.kind.expiry() {
+            label.push_str(&format!(" {}t", self.turns));
+        }
+        label
+    }
+}
+
+/// what a player's effects did as their turn started
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TurnTick {
+    pub damage: u8,
+    /// the effect that made them lose the turn
+    pub skipped: Option<StatusKind>,
+    pub expired: Vec<StatusKind>,
+}
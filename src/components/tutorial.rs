@@ -0,0 +1,216 @@
+//tutorial.rs
+//a scripted lesson: every step rigs the shotgun, says what to do, and only lets that action through
+use crate::components::items::Items;
+use crate::components::shotgun::Shell;
+use crate::event::AppEvent;
+
+/// health the dealer gets during the tutorial, so the lesson can't end early
+pub const TUTORIAL_DEALER_HEALTH: u8 = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TutorialAction {
+    ShootDealer,
+    ShootSelf,
+    /// just read the text and press enter
+    Continue,
+}
+
+#[derive(Debug, Clone)]
+pub struct TutorialStep {
+    /// replaces whatever is in the shotgun, first shell is fired first
+    pub load: Option<Vec<Shell>>,
+    /// handed to the player when the step starts
+    pub items: Vec<Items>,
+    pub text: &'static str,
+    pub expect: TutorialAction,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    steps: Vec<TutorialStep>,
+    step: usize,
+    /// explanations for shells and items showing up for the first time in this step
+    pub notes: Vec<&'static str>,
+    seen_shells: Vec<Shell>,
+    seen_items: Vec<Items>,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Tutorial {
+            steps: lesson(),
+            step: 0,
+            notes: Vec::new(),
+            seen_shells: Vec::new(),
+            seen_items: Vec::new(),
+        }
+    }
+
+    pub fn current(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.step)
+    }
+
+    /// (current step, total steps), both 1-based for display
+    pub fn progress(&self) -> (usize, usize) {
+        (self.step + 1, self.steps.len())
+    }
+
+    /// moves on and returns the new step, `None` once the lesson is over
+    pub fn advance(&mut self) -> Option<&TutorialStep> {
+        self.step += 1;
+        self.introduce();
+        self.current()
+    }
+
+    /// gathers the notes for the current step, called once when a step begins
+    pub fn introduce(&mut self) {
+        self.notes.clear();
+        let Some(step) = self.steps.get(self.step) else {
+            return;
+        };
+
+        for shell in step.load.iter().flatten() {
+            if !self.seen_shells.contains(shell) {
+                self.seen_shells.push(shell.clone());
+                self.notes.push(shell.describe());
+            }
+        }
+        for item in &step.items {
+            if !self.seen_items.contains(item) {
+                self.seen_items.push(*item);
+                self.notes.push(item.describe());
+            }
+        }
+    }
+
+    /// whether an event is allowed through: what the step expects, and whatever only looks around
+    pub fn allows(&self, event: &AppEvent) -> bool {
+        let expect = self.current().map(|step| step.expect);
+        match event {
+            AppEvent::Shoot => expect == Some(TutorialAction::ShootDealer),
+            AppEvent::ShootSelf => expect == Some(TutorialAction::ShootSelf),
+            AppEvent::TutorialNext => expect == Some(TutorialAction::Continue),
+            //the palette's commands come back through here as the events they stand for
+            AppEvent::Quit
+            | AppEvent::Pause
+            | AppEvent::Menu(_)
+            | AppEvent::QuitToTitle
+            | AppEvent::ToggleHelp
+            | AppEvent::Help(_)
+            | AppEvent::TogglePalette
+            | AppEvent::PaletteCommand(_)
+            | AppEvent::ToggleConsole
+            | AppEvent::SaveLog(_)
+            | AppEvent::Inventory(_)
+            | AppEvent::Inspect(_)
+            | AppEvent::Focus(_)
+            | AppEvent::Aim(_)
+            | AppEvent::Window(_)
+            | AppEvent::Show(_)
+            | AppEvent::ShowOdds
+            | AppEvent::ShowTracker
+            | AppEvent::FocusShotgun
+            | AppEvent::ScrollUp
+            | AppEvent::ScrollDown
+            | AppEvent::Log(_)
+            | AppEvent::ChangeFocus
+            | AppEvent::ChangeFocusBack => true,
+            _ => false,
+        }
+    }
+
+    /// what to press for the current step
+    pub fn hint(&self) -> &'static str {
+        match self.current().map(|step| step.expect) {
+            Some(TutorialAction::ShootDealer) => "press space to shoot the dealer",
+            Some(TutorialAction::ShootSelf) => "press x to shoot yourself",
+            _ => "press enter to continue",
+        }
+    }
+}
+
+impl Default for Tutorial {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lesson() -> Vec<TutorialStep> {
+    use TutorialAction::*;
+
+    let step = |load: Option<Vec<Shell>>, items: Vec<Items>, text, expect| TutorialStep { load, items, text, expect };
+    vec![
+        step(None, vec![],
+            "Welcome to the table. Each round the shotgun is loaded with a random mix of shells, \
+            and you and the dealer take turns firing it at yourselves or at each other.",
+            Continue),
+        step(Some(vec![Shell::Live, Shell::Blank]), vec![],
+            "This time the shotgun is rigged: the next shell is Live. Aim it at the dealer.",
+            ShootDealer),
+        step(Some(vec![Shell::Blank]), vec![],
+            "The next shell is a Blank. Shooting yourself with a Blank is safe and you keep the turn.",
+            ShootSelf),
+        step(Some(vec![Shell::Imposter]), vec![],
+            "The shell in the chamber is an Imposter. If you ever get to look at it, it will look like a Blank. \
+            Don't trust it, shoot the dealer.",
+            ShootDealer),
+        step(Some(vec![Shell::BeanBag]), vec![],
+            "A BeanBag won't hurt, but the dealer will be stunned. Shoot the dealer.",
+            ShootDealer),
+        step(Some(vec![Shell::Poison, Shell::Taser]), vec![],
+            "Poison hurts over the dealer's next turns, the Taser right away. The Poison is first, shoot the dealer.",
+            ShootDealer),
+        step(None, vec![],
+            "Then comes the Taser, shoot the dealer again.",
+            ShootDealer),
+        step(Some(vec![Shell::SelfDestruct]), vec![],
+            "A SelfDestruct shell hurts whoever pulls the trigger. Pull it anyway to see, it costs you one health.",
+            ShootDealer),
+        step(None, vec![Items::Saw, Items::Beer, Items::Cigarette, Items::MagnifyingGlass],
+            "Between rounds you are handed items. These four are the classics.",
+            Continue),
+        step(None, vec![Items::Mirror, Items::Inverter, Items::Handcuffs, Items::Adrenaline],
+            "These change who gets hurt and when.",
+            Continue),
+        step(None, vec![Items::Meth, Items::LSD, Items::AED],
+            "And these play with your health and what you can see. Your items show up in the inventory, press i.",
+            Continue),
+        step(None, vec![],
+            "That's everything. Press enter to leave the tutorial and start a real match.",
+            Continue),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::enums::ReloadAmount;
+
+    /// the tutorial moved on to the step that waits for a shot at the dealer
+    fn at_shoot_dealer() -> Tutorial {
+        let mut tutorial = Tutorial::new();
+        tutorial.advance();
+        assert_eq!(tutorial.current().map(|step| step.expect), Some(TutorialAction::ShootDealer));
+        tutorial
+    }
+
+    #[test]
+    fn only_the_expected_action_gets_through() {
+        let tutorial = at_shoot_dealer();
+        assert!(tutorial.allows(&AppEvent::Shoot));
+        assert!(!tutorial.allows(&AppEvent::ShootSelf));
+        assert!(!tutorial.allows(&AppEvent::TutorialNext));
+        assert!(tutorial.allows(&AppEvent::ToggleHelp));
+    }
+
+    #[test]
+    fn palette_and_console_actions_are_rejected_mid_lesson() {
+        let tutorial = at_shoot_dealer();
+        //`:reload` and `:use 1` from the palette
+        assert!(!tutorial.allows(&AppEvent::Reload(ReloadAmount::for_round(1))));
+        assert!(!tutorial.allows(&AppEvent::UseItem(0)));
+        assert!(!tutorial.allows(&AppEvent::ConsoleCommand("give 1 saw".to_string())));
+        assert!(!tutorial.allows(&AppEvent::ToggleSettings));
+        assert!(!tutorial.allows(&AppEvent::DealerTurn));
+    }
+}
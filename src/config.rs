@@ -0,0 +1,10 @@
+//config.rs
+use std::path::PathBuf;
+
+/// Directory holding everything the game saves locally, usually `~/.config/cli-roulette`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("cli-roulette"))
+}
@@ -1,20 +1,330 @@
 //data.rs
+use rand::{ rngs::StdRng, Rng, SeedableRng, thread_rng };
 
-use crate::components::shotgun::{Shotgun};
+use crate::components::shotgun::{Shotgun, Shell, ShotgunState};
 use crate::components::match_data::{MatchData};
+use crate::components::player::{Player};
+use crate::components::loot::{self, LootDeal};
+use crate::components::items::Items;
+use crate::components::status::StatusKind;
+use crate::rules::Rules;
+
+/// index of the human player in `Data.players`
+pub const PLAYER: usize = 0;
+/// index of the dealer in `Data.players`
+pub const DEALER: usize = 1;
 
 //need to implement things which would allow default and clone
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct Data {
     pub shotgun: Shotgun,
     pub match_data: MatchData,
+    pub players: Vec<Player>,
+    /// every random game decision comes from here, so a seed replays the same game
+    pub rng: StdRng,
+    /// what `rng` was last seeded with
+    pub seed: u64,
+    /// weights, reload sizes and health the game is played with
+    pub rules: Rules,
+}
+
+impl Default for Data {
+    fn default() -> Self {
+        Self::new(Rules::default())
+    }
 }
 
 impl Data {
-    pub fn new() -> Self {
+    pub fn new(rules: Rules) -> Self {
+        let seed = thread_rng().r#gen();
+        let health = rules.starting_health;
         Self {
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            rules,
             shotgun: Shotgun::new(),
             match_data: MatchData::new(),
+            players: vec![
+                Player::new("You", PLAYER as u8, health),
+                Player::new("Dealer", DEALER as u8, health),
+            ],
+        }
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// resets health, the shotgun and the turn order for a fresh match
+    pub fn new_match(&mut self) {
+        for player in self.players.iter_mut() {
+            player.set_health(self.rules.starting_health);
+            player.clear_items();
+            player.clear_statuses();
+            player.knowledge_mut().clear();
+        }
+        self.shotgun = Shotgun::new();
+        self.match_data = MatchData::new();
+        self.match_data.turn = Some(PLAYER);
+    }
+
+    /// hands every player their share of the item pool, as far as their inventory has room
+    pub fn deal_items(&mut self) -> Vec<LootDeal> {
+        let mut deals = Vec::new();
+        for idx in 0..self.players.len() {
+            let room = self.rules.inventory_size.saturating_sub(self.players[idx].items().len());
+            let count = self.rules.items_per_reload.min(room);
+            let items = loot::roll(&mut self.rng, &self.rules.item_pool, count);
+            for item in &items {
+                //there is room for every one of them
+                let _ = self.players[idx].give_item(*item, self.rules.inventory_size);
+            }
+            deals.push(LootDeal {
+                player: idx,
+                items,
+                capped: count < self.rules.items_per_reload,
+            });
+        }
+        deals
+    }
+
+    /// the last player standing, once everyone else is dead
+    pub fn winner(&self) -> Option<usize> {
+        let mut alive = self.players.iter().filter(|player| !player.is_dead());
+        match (alive.next(), alive.next()) {
+            (Some(player), None) => Some(player.id() as usize),
+            _ => None,
+        }
+    }
+
+    /// fires the next shell from `shooter` at `target`, returning what happened for the log
+    pub fn fire(&mut self, shooter: usize, target: usize) -> Vec<String> {
+        if self.shotgun.is_empty() {
+            return vec!["No shell in shotgun.".to_string()];
+        }
+
+        let mut log = Vec::new();
+        let mut target = target;
+        if shooter != target && self.players[target].take_status(StatusKind::Mirrored) {
+            log.push(format!("{}'s Mirror sends the shot back", self.players[target].name()));
+            target = shooter;
+        }
+        let Some(shell) = self.shotgun.shoot(shooter, Some(target)) else {
+            return log;
+        };
+        let shooter_name = self.players[shooter].name().to_string();
+        let target_name = self.players[target].name().to_string();
+        log.push(format!("{} shoots {}: {:?}", shooter_name, target_name, shell));
+
+        let damage = self.shotgun.damage();
+        let hurt = match shell {
+            Shell::Live | Shell::Imposter | Shell::Taser => Some(target),
+            Shell::SelfDestruct if matches!(self.shotgun.state, ShotgunState::Reinforced) => Some(target),
+            Shell::SelfDestruct => Some(shooter),
+            Shell::Blank | Shell::BeanBag | Shell::Poison => None,
+        };
+        //the slow shells work through status effects instead of damage
+        let effect = match shell {
+            Shell::BeanBag => Some(StatusKind::Stunned),
+            Shell::Poison => Some(StatusKind::Poisoned),
+            _ => None,
+        };
+        if let Some(kind) = effect {
+            self.players[target].add_status(kind);
+            log.push(format!("{} is {}", target_name, kind.describe()));
+        }
+        if let Some(idx) = hurt {
+            if self.players[idx].take_status(StatusKind::Aed) {
+                log.push(format!("The AED keeps {} going, no health lost", self.players[idx].name()));
+            } else {
+                self.players[idx].damage(damage);
+                log.push(format!("{} loses {} health", self.players[idx].name(), damage));
+            }
+        }
+
+        //the saw only lasts for one shot
+        if matches!(self.shotgun.state, ShotgunState::SawedOff) {
+            self.shotgun.state = ShotgunState::Default;
+        }
+
+        //shooting yourself with a blank keeps the turn
+        if !(shooter == target && shell == Shell::Blank) {
+            log.extend(self.pass_turn(shooter));
+        }
+        log
+    }
+
+    /// hands the turn on from `current`, ticking the effects of whoever is next
+    /// and skipping past anyone who is stunned or cuffed
+    fn pass_turn(&mut self, current: usize) -> Vec<String> {
+        let mut log = Vec::new();
+        let mut next = (current + 1) % self.players.len();
+        //everyone gets a tick, but somebody has to end up with the turn
+        for _ in 0..self.players.len() {
+            let tick = self.players[next].tick_statuses();
+            let name = self.players[next].name().to_string();
+            if tick.damage > 0 {
+                log.push(format!("{} -{} health from poison", name, tick.damage));
+            }
+            for kind in &tick.expired {
+                log.push(format!("{} is no longer {}", name, kind.describe()));
+            }
+            match tick.skipped {
+                Some(kind) if !self.players[next].is_dead() => {
+                    log.push(format!("{} is {} and skips a turn", name, kind.describe()));
+                    next = (next + 1) % self.players.len();
+                },
+                _ => break,
+            }
+        }
+        self.match_data.turn = Some(next);
+        log
+    }
+
+    /// settles what was left from the round before the shotgun is loaded again
+    pub fn end_round(&mut self) -> Vec<String> {
+        let mut log = Vec::new();
+        //an AED that was never needed costs its owner
+        for player in self.players.iter_mut() {
+            if player.end_round_statuses().contains(&StatusKind::Aed) {
+                player.damage(1);
+                log.push(format!("{} wasn't shot, the AED takes 1 health", player.name()));
+            }
+        }
+        log
+    }
+
+    /// uses the item in `user`'s inventory `slot`, the error says why nothing happened
+    pub fn use_item(&mut self, user: usize, slot: usize) -> Result<Vec<String>, String> {
+        let Some(item) = self.players[user].items().get(slot).copied() else {
+            return Err(format!("Inventory slot {} is empty", slot + 1));
+        };
+        let log = self.apply_item(user, item)?;
+        self.players[user].take_item(slot);
+        Ok(log)
+    }
+
+    /// uses the Adrenaline in `user`'s `slot` on the other player's item in `target`
+    pub fn use_adrenaline(&mut self, user: usize, slot: usize, target: usize) -> Result<Vec<String>, String> {
+        if self.players[user].items().get(slot) != Some(&Items::Adrenaline) {
+            return Err(format!("There is no Adrenaline in slot {}", slot + 1));
+        }
+        let log = self.steal(user, target)?;
+        self.players[user].take_item(slot);
+        Ok(log)
+    }
+
+    /// takes the other player's item in `target` and uses it right away
+    fn steal(&mut self, user: usize, target: usize) -> Result<Vec<String>, String> {
+        let other = (user + 1) % self.players.len();
+        let stolen = match self.players[other].items().get(target) {
+            Some(Items::Adrenaline) => return Err("Adrenaline can't steal Adrenaline".to_string()),
+            Some(item) => *item,
+            None => return Err(format!("{} has nothing in that slot", self.players[other].name())),
+        };
+        let mut log = vec![format!("{} steals {}'s {}", self.players[user].name(), self.players[other].name(), stolen.name())];
+        log.extend(self.apply_item(user, stolen)?);
+        self.players[other].take_item(target);
+        Ok(log)
+    }
+
+    /// the effect of `item`, checked before anything changes so a refusal costs nothing
+    fn apply_item(&mut self, user: usize, item: Items) -> Result<Vec<String>, String> {
+        let name = self.players[user].name().to_string();
+        let other = (user + 1) % self.players.len();
+        let max_health = self.rules.starting_health;
+        if self.shotgun.is_empty() && matches!(item, Items::Saw | Items::Beer | Items::Inverter | Items::MagnifyingGlass | Items::LSD) {
+            return Err(format!("The shotgun is empty, the {} would be wasted", item.name()));
+        }
+
+        let log = match item {
+            Items::Saw => {
+                if matches!(self.shotgun.state, ShotgunState::ThickBarrel) {
+                    return Err("The barrel is too thick to saw off".to_string());
+                }
+                self.shotgun.state = ShotgunState::SawedOff;
+                vec![format!("{} saws off the barrel, the next shot does double damage", name)]
+            },
+            Items::Beer => {
+                let shell = self.shotgun.shoot(user, None);
+                vec![format!("{} racks the shotgun, out comes a {:?}", name, shell.unwrap_or_default())]
+            },
+            Items::Cigarette => {
+                self.players[user].heal(1, max_health);
+                vec![format!("{} smokes a cigarette, {} health", name, self.players[user].health())]
+            },
+            Items::Mirror => {
+                self.players[user].add_status(StatusKind::Mirrored);
+                vec![format!("{} props up a Mirror", name)]
+            },
+            Items::Inverter => {
+                self.shotgun.invert();
+                //the user flips what they believe, everyone else can't trust what they saw
+                for (idx, player) in self.players.iter_mut().enumerate() {
+                    let believed = player.knowledge().at(&self.shotgun, 0).cloned();
+                    match (idx == user, believed) {
+                        (true, Some(Shell::Live)) => player.knowledge_mut().record(&self.shotgun, 0, Shell::Blank),
+                        (true, Some(Shell::Blank)) => player.knowledge_mut().record(&self.shotgun, 0, Shell::Live),
+                        (true, _) => {},
+                        (false, _) => player.knowledge_mut().forget(&self.shotgun, 0),
+                    }
+                }
+                vec![format!("{} flips the polarity of the next shell", name)]
+            },
+            Items::MagnifyingGlass => {
+                if self.players[user].has_status(StatusKind::Blind) {
+                    return Err("The Meth makes it impossible to see the next shell".to_string());
+                }
+                vec![self.reveal(user, &name, 0)]
+            },
+            Items::Handcuffs => {
+                if self.players[other].has_status(StatusKind::Cuffed) {
+                    return Err(format!("{} is already cuffed", self.players[other].name()));
+                }
+                self.players[other].add_status(StatusKind::Cuffed);
+                vec![format!("{} cuffs {}, they skip their next turn", name, self.players[other].name())]
+            },
+            Items::Meth => {
+                self.players[user].heal(2, max_health);
+                self.players[user].add_status(StatusKind::Blind);
+                self.players[user].knowledge_mut().clear();
+                vec![format!("{} takes Meth, {} health but can't see straight", name, self.players[user].health())]
+            },
+            Items::AED => {
+                if self.players[user].has_status(StatusKind::Aed) {
+                    return Err(format!("{}'s AED is already charged this round", name));
+                }
+                self.players[user].add_status(StatusKind::Aed);
+                vec![format!("{} charges an AED", name)]
+            },
+            Items::Adrenaline => {
+                let Some(target) = self.players[other].items().iter().position(|item| *item != Items::Adrenaline) else {
+                    return Err(format!("{} has nothing worth stealing", self.players[other].name()));
+                };
+                self.steal(user, target)?
+            },
+            Items::LSD => {
+                let shells = self.shotgun.shells.borrow().len();
+                let depth = if shells > 1 { self.rng.gen_range(1..shells) } else { 0 };
+                vec![self.reveal(user, &name, depth)]
+            },
+        };
+        Ok(log)
+    }
+
+    /// shows `user` the shell `depth` shots away, only the player gets told what it is.
+    /// They see what it looks like, so an Imposter is remembered as a Blank.
+    fn reveal(&mut self, user: usize, name: &str, depth: usize) -> String {
+        let shell = {
+            let shells = self.shotgun.shells.borrow();
+            shells[shells.len() - 1 - depth].apparent()
+        };
+        self.players[user].knowledge_mut().record(&self.shotgun, depth, shell.clone());
+        match (user, depth) {
+            (PLAYER, 0) => format!("The next shell is {:?}", shell),
+            (PLAYER, _) => format!("Shell {} from now is {:?}", depth + 1, shell),
+            _ => format!("{} takes a look inside the shotgun", name),
         }
     }
 }
@@ -1,10 +1,20 @@
 use color_eyre::eyre::OptionExt;
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 use crate::components::enums::ReloadAmount;
+use crate::components::sandbox::SandboxInput;
+use crate::ui_components::inspector::InspectorInput;
+use crate::ui_components::inventory::InventoryInput;
+use crate::ui_components::logger::LogInput;
+use crate::ui_components::settings::SettingsInput;
+use crate::screens::MenuInput;
+use crate::ui_components::ui_help::HelpInput;
+use crate::ui_components::widget_data::WidgetKind;
+use crate::ui_components::windows::WindowInput;
 
 /// The frequency at which tick events are emitted.
 const TICK_FPS: f64 = 30.0;
@@ -41,23 +51,73 @@ pub enum AppEvent {
     Reload(ReloadAmount),
     /// Shoot
     Shoot,
+    /// Shoot yourself
+    ShootSelf,
+    /// Use the item in an inventory slot
+    UseItem(usize),
+    /// Use the Adrenaline in `slot` on the dealer's item in `target`
+    Steal { slot: usize, target: usize },
+    /// Move the inventory cursor
+    Inventory(InventoryInput),
+    /// Let the dealer take its turn
+    DealerTurn,
+    /// Answer to the "double or nothing" prompt after winning a match
+    DoubleOrNothing(bool),
+    /// Move on to the next tutorial step
+    TutorialNext,
+    /// Edit the sandbox table
+    Sandbox(SandboxInput),
+    /// Open or close the developer console
+    ToggleConsole,
+    /// Run a line typed into the developer console
+    ConsoleCommand(String),
+    /// Navigate the data inspector
+    Inspect(InspectorInput),
+    /// Open or close the settings screen
+    ToggleSettings,
+    /// Pick, edit or save a preset in the settings screen
+    Settings(SettingsInput),
+    /// Save the settings draft as a preset with this name
+    SavePreset(String),
+    /// Move around a menu screen
+    Menu(MenuInput),
+    /// Open the pause menu over the table
+    Pause,
+    /// Abandon the run and go back to the title menu
+    QuitToTitle,
+    /// Open or close the help overlay
+    ToggleHelp,
+    /// Open or close the command palette
+    TogglePalette,
+    /// Run a line typed into the command palette
+    PaletteCommand(String),
+    /// Write the log to a file, `None` for the default file
+    SaveLog(Option<PathBuf>),
+    /// Focus a window and bring it to the front, sent by a click
+    Focus(WidgetKind),
+    /// Aim the shotgun at a player, sent by a click
+    Aim(usize),
+    /// Move or resize the focused window
+    Window(WindowInput),
+    /// Page through or scroll the help overlay
+    Help(HelpInput),
 
 
     ///UI EVENTS
-   /// Popup screen
-    ShowData,
-    /// Show log
-    ShowLog,
-    /// Show inventory
-    ShowInventory,
-    /// Show player
-    ShowPlayer,
+    /// Show or hide a pane
+    Show(WidgetKind),
+    /// Show the odds of the next shell
+    ShowOdds,
+    /// Show the shells spent this load
+    ShowTracker,
     /// Show shotgun
     FocusShotgun,
     /// Scroll the log up
     ScrollUp,
     /// Scroll the log down
     ScrollDown,
+    /// Filter the log or show its debug messages
+    Log(LogInput),
     /// Change focus
     ChangeFocus,
     /// Change focus back
@@ -73,6 +133,12 @@ pub struct EventHandler {
     receiver: mpsc::UnboundedReceiver<Event>,
 }
 
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
     pub fn new() -> Self {
@@ -0,0 +1,840 @@
+//keymap.rs
+//every key the game reacts to lives in one table, the input handler and the help overlay both read it
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use color_eyre::eyre::{bail, WrapErr};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::app::App;
+use crate::config::config_dir;
+use crate::components::sandbox::SandboxInput;
+use crate::event::AppEvent;
+use crate::rules::MAX_INVENTORY_SIZE;
+use crate::screens::MenuInput;
+use crate::ui_components::inspector::InspectorInput;
+use crate::ui_components::inventory::InventoryInput;
+use crate::ui_components::settings::SettingsInput;
+use crate::ui_components::ui_help::HelpInput;
+use crate::ui_components::widget_data::WidgetKind;
+use crate::ui_components::logger::LogInput;
+use crate::ui_components::windows::WindowInput;
+
+const KEYS_FILE: &str = "keys.toml";
+
+/// where a binding applies, several contexts are active at once at the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// everywhere
+    Global,
+    /// while the help overlay is open, it takes the keys from the screen below
+    Help,
+    /// title, setup, pause and game over menus
+    Menu,
+    Settings,
+    /// the "double or nothing" question after a won match
+    Confirmation,
+    /// at the table
+    Table,
+    /// at the table during the tutorial
+    Tutorial,
+    /// the log has focus
+    Log,
+    /// the data inspector has focus
+    Data,
+    /// the inventory has focus
+    Inventory,
+    /// a window that can be moved has focus
+    Window,
+    /// at the sandbox table
+    Sandbox,
+}
+
+impl Context {
+    pub const ALL: [Context; 12] = [
+        Context::Global,
+        Context::Help,
+        Context::Menu,
+        Context::Settings,
+        Context::Confirmation,
+        Context::Table,
+        Context::Tutorial,
+        Context::Log,
+        Context::Data,
+        Context::Inventory,
+        Context::Window,
+        Context::Sandbox,
+    ];
+
+    /// the section name in the keys file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Context::Global => "global",
+            Context::Help => "help",
+            Context::Menu => "menu",
+            Context::Settings => "settings",
+            Context::Confirmation => "confirmation",
+            Context::Table => "table",
+            Context::Tutorial => "tutorial",
+            Context::Log => "log",
+            Context::Data => "data",
+            Context::Inventory => "inventory",
+            Context::Window => "window",
+            Context::Sandbox => "sandbox",
+        }
+    }
+
+    /// whether both can be active at once with neither winning by design,
+    /// a focused window shadowing the table's keys is on purpose
+    pub fn overlaps(&self, other: Context) -> bool {
+        use Context as C;
+        *self == other || matches!(
+            (self, other),
+            (C::Global, _) | (_, C::Global) | (C::Table, C::Tutorial | C::Sandbox) | (C::Tutorial | C::Sandbox, C::Table)
+                | (C::Window, C::Log | C::Data | C::Inventory) | (C::Log | C::Data | C::Inventory, C::Window)
+        )
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Context::Global => "Anywhere",
+            Context::Help => "Help",
+            Context::Menu => "Menus",
+            Context::Settings => "Settings",
+            Context::Confirmation => "Double or nothing",
+            Context::Table => "Table",
+            Context::Tutorial => "Tutorial",
+            Context::Log => "Log focused",
+            Context::Data => "Data focused",
+            Context::Inventory => "Inventory focused",
+            Context::Window => "Any window focused",
+            Context::Sandbox => "Sandbox",
+        }
+    }
+}
+
+/// a key together with the modifiers held down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub const fn key(code: KeyCode) -> Self {
+        KeyChord { code, modifiers: KeyModifiers::NONE }
+    }
+
+    pub const fn ctrl(code: KeyCode) -> Self {
+        KeyChord { code, modifiers: KeyModifiers::CONTROL }
+    }
+
+    pub fn matches(&self, key_event: &KeyEvent) -> bool {
+        self.same_key(&KeyChord { code: key_event.code, modifiers: key_event.modifiers })
+    }
+
+    /// whether both chords are pressed the same way
+    pub fn same_key(&self, other: &KeyChord) -> bool {
+        match (self.code, other.code) {
+            //letters match in either case, for other characters shift is part of the character
+            (KeyCode::Char(a), KeyCode::Char(b)) => {
+                a.eq_ignore_ascii_case(&b) && self.modifiers - KeyModifiers::SHIFT == other.modifiers - KeyModifiers::SHIFT
+            },
+            (a, b) => a == b && self.modifiers == other.modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    /// reads keys as the help overlay shows them: `q`, `space`, `ctrl+tab`, `shift+up`, `f1`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        //a lone `+` is a key, not a modifier separator
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{}` in `{}`", modifier, text)),
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c.to_ascii_lowercase()),
+            _ => match rest.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{}`", text)),
+                },
+            },
+        };
+        //shift is already part of a character
+        if matches!(code, KeyCode::Char(_)) {
+            modifiers -= KeyModifiers::SHIFT;
+        }
+        Ok(KeyChord { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// what a key does, turned into an [`AppEvent`] when pressed
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    Quit,
+    Pause,
+    ToggleConsole,
+    ToggleHelp,
+    TogglePalette,
+    Help(HelpInput),
+    Menu(MenuInput),
+    ToggleSettings,
+    Settings(SettingsInput),
+    DoubleOrNothing(bool),
+    ShowData,
+    ShowLog,
+    ShowInventory,
+    ShowPlayer,
+    ShowOdds,
+    ShowTracker,
+    FocusShotgun,
+    FocusNext,
+    FocusPrev,
+    Reload,
+    Shoot,
+    ShootSelf,
+    /// the item in a fixed slot
+    UseSlot(usize),
+    /// the item under the inventory cursor
+    UseSelected,
+    TutorialNext,
+    ScrollUp,
+    ScrollDown,
+    Log(LogInput),
+    Inspect(InspectorInput),
+    Inventory(InventoryInput),
+    Window(WindowInput),
+    Sandbox(SandboxInput),
+}
+
+impl Action {
+    pub fn event(&self, app: &App) -> AppEvent {
+        match *self {
+            Action::Quit => AppEvent::Quit,
+            Action::Pause => AppEvent::Pause,
+            Action::ToggleConsole => AppEvent::ToggleConsole,
+            Action::ToggleHelp => AppEvent::ToggleHelp,
+            Action::TogglePalette => AppEvent::TogglePalette,
+            Action::Help(input) => AppEvent::Help(input),
+            Action::Menu(input) => AppEvent::Menu(input),
+            Action::ToggleSettings => AppEvent::ToggleSettings,
+            Action::Settings(input) => AppEvent::Settings(input),
+            Action::DoubleOrNothing(keep_going) => AppEvent::DoubleOrNothing(keep_going),
+            Action::ShowData => AppEvent::Show(WidgetKind::Data),
+            Action::ShowLog => AppEvent::Show(WidgetKind::Log),
+            Action::ShowInventory => AppEvent::Show(WidgetKind::Inventory),
+            Action::ShowPlayer => AppEvent::Show(WidgetKind::Player),
+            Action::ShowOdds => AppEvent::ShowOdds,
+            Action::ShowTracker => AppEvent::ShowTracker,
+            Action::FocusShotgun => AppEvent::FocusShotgun,
+            Action::FocusNext => AppEvent::ChangeFocus,
+            Action::FocusPrev => AppEvent::ChangeFocusBack,
+            Action::Reload => AppEvent::Reload(app.reload_amount()),
+            Action::Shoot => AppEvent::Shoot,
+            Action::ShootSelf => AppEvent::ShootSelf,
+            Action::UseSlot(slot) => AppEvent::UseItem(slot),
+            Action::UseSelected => AppEvent::UseItem(app.inventory.cursor),
+            Action::TutorialNext => AppEvent::TutorialNext,
+            Action::ScrollUp => AppEvent::ScrollUp,
+            Action::ScrollDown => AppEvent::ScrollDown,
+            Action::Log(input) => AppEvent::Log(input),
+            Action::Inspect(input) => AppEvent::Inspect(input),
+            Action::Inventory(input) => AppEvent::Inventory(input),
+            Action::Window(input) => AppEvent::Window(input),
+            Action::Sandbox(input) => AppEvent::Sandbox(input),
+        }
+    }
+
+    /// the name used in the keys file
+    pub fn name(&self) -> String {
+        let name = match self {
+            Action::Quit => "quit",
+            Action::Pause => "pause",
+            Action::ToggleConsole => "console",
+            Action::ToggleHelp => "help",
+            Action::TogglePalette => "palette",
+            Action::Help(HelpInput::Next) => "next_page",
+            Action::Help(HelpInput::Prev) => "prev_page",
+            Action::Help(HelpInput::Up) => "up",
+            Action::Help(HelpInput::Down) => "down",
+            Action::Menu(MenuInput::Up) => "up",
+            Action::Menu(MenuInput::Down) => "down",
+            Action::Menu(MenuInput::Left) => "left",
+            Action::Menu(MenuInput::Right) => "right",
+            Action::Menu(MenuInput::Select) => "select",
+            Action::Menu(MenuInput::Back) => "back",
+            Action::ToggleSettings => "settings",
+            Action::Settings(SettingsInput::Up) => "up",
+            Action::Settings(SettingsInput::Down) => "down",
+            Action::Settings(SettingsInput::Left) => "left",
+            Action::Settings(SettingsInput::Right) => "right",
+            Action::Settings(SettingsInput::Select) => "select",
+            Action::Settings(SettingsInput::Save) => "save",
+            Action::DoubleOrNothing(true) => "double",
+            Action::DoubleOrNothing(false) => "cash_out",
+            Action::ShowData => "data",
+            Action::ShowLog => "log",
+            Action::ShowInventory => "inventory",
+            Action::ShowPlayer => "player",
+            Action::ShowOdds => "odds",
+            Action::ShowTracker => "tracker",
+            Action::FocusShotgun => "focus_shotgun",
+            Action::FocusNext => "focus_next",
+            Action::FocusPrev => "focus_prev",
+            Action::Reload => "reload",
+            Action::Shoot => "shoot",
+            Action::ShootSelf => "shoot_self",
+            Action::UseSlot(slot) => return format!("use_slot_{}", slot + 1),
+            Action::UseSelected => "use",
+            Action::TutorialNext => "next",
+            Action::ScrollUp => "scroll_up",
+            Action::ScrollDown => "scroll_down",
+            Action::Log(LogInput::Filter) => "filter",
+            Action::Log(LogInput::Debug) => "debug",
+            Action::Log(LogInput::PageUp) => "page_up",
+            Action::Log(LogInput::PageDown) => "page_down",
+            Action::Log(LogInput::Oldest) => "oldest",
+            Action::Log(LogInput::Newest) => "newest",
+            Action::Log(LogInput::Search) => "search",
+            Action::Log(LogInput::OlderMatch) => "older_match",
+            Action::Log(LogInput::NewerMatch) => "newer_match",
+            Action::Inspect(InspectorInput::Up) => "up",
+            Action::Inspect(InspectorInput::Down) => "down",
+            Action::Inspect(InspectorInput::Expand) => "expand",
+            Action::Inspect(InspectorInput::Collapse) => "collapse",
+            Action::Inspect(InspectorInput::Toggle) => "toggle",
+            Action::Inventory(InventoryInput::Up) => "up",
+            Action::Inventory(InventoryInput::Down) => "down",
+            Action::Inventory(InventoryInput::Left) => "left",
+            Action::Inventory(InventoryInput::Right) => "right",
+            Action::Window(WindowInput::Left) => "move_left",
+            Action::Window(WindowInput::Right) => "move_right",
+            Action::Window(WindowInput::Up) => "move_up",
+            Action::Window(WindowInput::Down) => "move_down",
+            Action::Window(WindowInput::Wider) => "wider",
+            Action::Window(WindowInput::Narrower) => "narrower",
+            Action::Window(WindowInput::Taller) => "taller",
+            Action::Window(WindowInput::Shorter) => "shorter",
+            Action::Window(WindowInput::Reset) => "reset_window",
+            Action::Sandbox(SandboxInput::Up) => "up",
+            Action::Sandbox(SandboxInput::Down) => "down",
+            Action::Sandbox(SandboxInput::Left) => "left",
+            Action::Sandbox(SandboxInput::Right) => "right",
+            Action::Sandbox(SandboxInput::Apply) => "apply",
+            Action::Sandbox(SandboxInput::Remove) => "remove",
+        };
+        name.to_string()
+    }
+
+    /// what the help overlay says about it, keys with the same text are listed together
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Pause => "pause",
+            Action::ToggleConsole => "developer console",
+            Action::ToggleHelp => "open or close this help",
+            Action::TogglePalette => "command palette, type an action by name",
+            Action::Help(HelpInput::Next) => "next page",
+            Action::Help(HelpInput::Prev) => "previous page",
+            Action::Help(HelpInput::Up) => "scroll up",
+            Action::Help(HelpInput::Down) => "scroll down",
+            Action::Menu(MenuInput::Up) => "move up",
+            Action::Menu(MenuInput::Down) => "move down",
+            Action::Menu(MenuInput::Left) => "previous choice",
+            Action::Menu(MenuInput::Right) => "next choice",
+            Action::Menu(MenuInput::Select) => "pick",
+            Action::Menu(MenuInput::Back) => "go back",
+            Action::ToggleSettings => "open or close the settings",
+            Action::Settings(SettingsInput::Up) => "move up",
+            Action::Settings(SettingsInput::Down) => "move down",
+            Action::Settings(SettingsInput::Left) => "lower the value",
+            Action::Settings(SettingsInput::Right) => "raise the value",
+            Action::Settings(SettingsInput::Select) => "play the selected preset",
+            Action::Settings(SettingsInput::Save) => "save the draft as a preset",
+            Action::DoubleOrNothing(true) => "double or nothing",
+            Action::DoubleOrNothing(false) => "cash out",
+            Action::ShowData => "data inspector",
+            Action::ShowLog => "log",
+            Action::ShowInventory => "inventory",
+            Action::ShowPlayer => "player",
+            Action::ShowOdds => "odds panel",
+            Action::ShowTracker => "spent shell tracker",
+            Action::FocusShotgun => "focus the shotgun",
+            Action::FocusNext => "focus the next window",
+            Action::FocusPrev => "focus the previous window",
+            Action::Reload => "reload",
+            Action::Shoot => "shoot the dealer",
+            Action::ShootSelf => "shoot yourself",
+            Action::UseSlot(_) => "use the item in that slot",
+            Action::UseSelected => "use the selected item",
+            Action::TutorialNext => "next step",
+            Action::ScrollUp => "scroll up",
+            Action::ScrollDown => "scroll down",
+            Action::Log(LogInput::Filter) => "show one kind of message, then all again",
+            Action::Log(LogInput::Debug) => "show or hide debug messages",
+            Action::Log(LogInput::PageUp) => "a page up",
+            Action::Log(LogInput::PageDown) => "a page down",
+            Action::Log(LogInput::Oldest) => "the oldest message",
+            Action::Log(LogInput::Newest) => "the newest message, following new ones",
+            Action::Log(LogInput::Search) => "search, enter keeps the matches and esc clears them",
+            Action::Log(LogInput::OlderMatch) => "the match before",
+            Action::Log(LogInput::NewerMatch) => "the match after",
+            Action::Inspect(InspectorInput::Up) => "move up",
+            Action::Inspect(InspectorInput::Down) => "move down",
+            Action::Inspect(InspectorInput::Expand) => "expand",
+            Action::Inspect(InspectorInput::Collapse) => "collapse",
+            Action::Inspect(InspectorInput::Toggle) => "expand or collapse",
+            Action::Inventory(InventoryInput::Up) => "move up",
+            Action::Inventory(InventoryInput::Down) => "move down",
+            Action::Inventory(InventoryInput::Left) => "move left",
+            Action::Inventory(InventoryInput::Right) => "move right",
+            Action::Window(WindowInput::Left) => "move the window left",
+            Action::Window(WindowInput::Right) => "move the window right",
+            Action::Window(WindowInput::Up) => "move the window up",
+            Action::Window(WindowInput::Down) => "move the window down",
+            Action::Window(WindowInput::Wider) => "make the window wider",
+            Action::Window(WindowInput::Narrower) => "make the window narrower",
+            Action::Window(WindowInput::Taller) => "make the window taller",
+            Action::Window(WindowInput::Shorter) => "make the window shorter",
+            Action::Window(WindowInput::Reset) => "put the window back where it opens",
+            Action::Sandbox(SandboxInput::Up) => "move up",
+            Action::Sandbox(SandboxInput::Down) => "move down",
+            Action::Sandbox(SandboxInput::Left) => "previous value",
+            Action::Sandbox(SandboxInput::Right) => "next value",
+            Action::Sandbox(SandboxInput::Apply) => "run the selected row",
+            Action::Sandbox(SandboxInput::Remove) => "drop the last queued shell",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    pub context: Context,
+    pub chord: KeyChord,
+    pub action: Action,
+}
+
+/// the keys for one action in the keys file, a single key or a list
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        use Action as A;
+        use Context as C;
+        use KeyCode as K;
+
+        let key = |context, code, action| Binding { context, chord: KeyChord::key(code), action };
+        let shift = |context, code, action| Binding { context, chord: KeyChord { code, modifiers: KeyModifiers::SHIFT }, action };
+        let alt = |context, code, action| Binding { context, chord: KeyChord { code, modifiers: KeyModifiers::ALT }, action };
+        let mut bindings = vec![
+            Binding { context: C::Global, chord: KeyChord::ctrl(K::Char('c')), action: A::Quit },
+            key(C::Global, K::Char('`'), A::ToggleConsole),
+            key(C::Global, K::Char('?'), A::ToggleHelp),
+            key(C::Global, K::Char(':'), A::TogglePalette),
+
+            key(C::Help, K::Esc, A::ToggleHelp),
+            key(C::Help, K::Right, A::Help(HelpInput::Next)),
+            key(C::Help, K::Char('l'), A::Help(HelpInput::Next)),
+            key(C::Help, K::Left, A::Help(HelpInput::Prev)),
+            key(C::Help, K::Char('h'), A::Help(HelpInput::Prev)),
+            key(C::Help, K::Up, A::Help(HelpInput::Up)),
+            key(C::Help, K::Char('k'), A::Help(HelpInput::Up)),
+            key(C::Help, K::Down, A::Help(HelpInput::Down)),
+            key(C::Help, K::Char('j'), A::Help(HelpInput::Down)),
+
+            key(C::Menu, K::Up, A::Menu(MenuInput::Up)),
+            key(C::Menu, K::Char('k'), A::Menu(MenuInput::Up)),
+            key(C::Menu, K::Down, A::Menu(MenuInput::Down)),
+            key(C::Menu, K::Char('j'), A::Menu(MenuInput::Down)),
+            key(C::Menu, K::Left, A::Menu(MenuInput::Left)),
+            key(C::Menu, K::Char('h'), A::Menu(MenuInput::Left)),
+            key(C::Menu, K::Right, A::Menu(MenuInput::Right)),
+            key(C::Menu, K::Char('l'), A::Menu(MenuInput::Right)),
+            key(C::Menu, K::Enter, A::Menu(MenuInput::Select)),
+            key(C::Menu, K::Char(' '), A::Menu(MenuInput::Select)),
+            key(C::Menu, K::Esc, A::Menu(MenuInput::Back)),
+            key(C::Menu, K::Char('q'), A::Quit),
+
+            key(C::Settings, K::Esc, A::ToggleSettings),
+            key(C::Settings, K::Char('o'), A::ToggleSettings),
+            key(C::Settings, K::Up, A::Settings(SettingsInput::Up)),
+            key(C::Settings, K::Down, A::Settings(SettingsInput::Down)),
+            key(C::Settings, K::Left, A::Settings(SettingsInput::Left)),
+            key(C::Settings, K::Right, A::Settings(SettingsInput::Right)),
+            key(C::Settings, K::Enter, A::Settings(SettingsInput::Select)),
+            key(C::Settings, K::Char('w'), A::Settings(SettingsInput::Save)),
+
+            key(C::Confirmation, K::Char('y'), A::DoubleOrNothing(true)),
+            key(C::Confirmation, K::Char('n'), A::DoubleOrNothing(false)),
+            key(C::Confirmation, K::Esc, A::Quit),
+            key(C::Confirmation, K::Char('q'), A::Quit),
+
+            key(C::Table, K::Esc, A::Pause),
+            key(C::Table, K::Char('q'), A::Quit),
+            key(C::Table, K::Char(' '), A::Shoot),
+            key(C::Table, K::Char('x'), A::ShootSelf),
+            key(C::Table, K::Char('r'), A::Reload),
+            key(C::Table, K::Char('d'), A::ShowData),
+            key(C::Table, K::Char('l'), A::ShowLog),
+            key(C::Table, K::Char('i'), A::ShowInventory),
+            key(C::Table, K::Char('p'), A::ShowPlayer),
+            key(C::Table, K::Char('o'), A::ToggleSettings),
+            key(C::Table, K::Char('h'), A::ShowOdds),
+            key(C::Table, K::Char('t'), A::ShowTracker),
+            key(C::Table, K::Char('s'), A::FocusShotgun),
+            Binding { context: C::Table, chord: KeyChord::ctrl(K::Tab), action: A::FocusPrev },
+            key(C::Table, K::Tab, A::FocusNext),
+        ];
+        for (slot, digit) in ('1'..='9').take(MAX_INVENTORY_SIZE).enumerate() {
+            bindings.push(key(C::Table, K::Char(digit), A::UseSlot(slot)));
+        }
+        bindings.extend([
+            key(C::Tutorial, K::Enter, A::TutorialNext),
+
+            key(C::Log, K::Char('k'), A::ScrollUp),
+            key(C::Log, K::Char('j'), A::ScrollDown),
+            key(C::Log, K::Char('f'), A::Log(LogInput::Filter)),
+            key(C::Log, K::Char('v'), A::Log(LogInput::Debug)),
+            key(C::Log, K::PageUp, A::Log(LogInput::PageUp)),
+            key(C::Log, K::PageDown, A::Log(LogInput::PageDown)),
+            key(C::Log, K::Home, A::Log(LogInput::Oldest)),
+            key(C::Log, K::End, A::Log(LogInput::Newest)),
+            key(C::Log, K::Char('/'), A::Log(LogInput::Search)),
+            key(C::Log, K::Char('n'), A::Log(LogInput::OlderMatch)),
+            key(C::Log, K::Char('b'), A::Log(LogInput::NewerMatch)),
+
+            key(C::Data, K::Up, A::Inspect(InspectorInput::Up)),
+            key(C::Data, K::Down, A::Inspect(InspectorInput::Down)),
+            key(C::Data, K::Right, A::Inspect(InspectorInput::Expand)),
+            key(C::Data, K::Left, A::Inspect(InspectorInput::Collapse)),
+            key(C::Data, K::Enter, A::Inspect(InspectorInput::Toggle)),
+
+            key(C::Inventory, K::Up, A::Inventory(InventoryInput::Up)),
+            key(C::Inventory, K::Down, A::Inventory(InventoryInput::Down)),
+            key(C::Inventory, K::Left, A::Inventory(InventoryInput::Left)),
+            key(C::Inventory, K::Right, A::Inventory(InventoryInput::Right)),
+            key(C::Inventory, K::Enter, A::UseSelected),
+
+            shift(C::Window, K::Left, A::Window(WindowInput::Left)),
+            shift(C::Window, K::Right, A::Window(WindowInput::Right)),
+            shift(C::Window, K::Up, A::Window(WindowInput::Up)),
+            shift(C::Window, K::Down, A::Window(WindowInput::Down)),
+            alt(C::Window, K::Right, A::Window(WindowInput::Wider)),
+            alt(C::Window, K::Left, A::Window(WindowInput::Narrower)),
+            alt(C::Window, K::Down, A::Window(WindowInput::Taller)),
+            alt(C::Window, K::Up, A::Window(WindowInput::Shorter)),
+            alt(C::Window, K::Char('r'), A::Window(WindowInput::Reset)),
+
+            key(C::Sandbox, K::Up, A::Sandbox(SandboxInput::Up)),
+            key(C::Sandbox, K::Down, A::Sandbox(SandboxInput::Down)),
+            key(C::Sandbox, K::Left, A::Sandbox(SandboxInput::Left)),
+            key(C::Sandbox, K::Right, A::Sandbox(SandboxInput::Right)),
+            key(C::Sandbox, K::Enter, A::Sandbox(SandboxInput::Apply)),
+            key(C::Sandbox, K::Backspace, A::Sandbox(SandboxInput::Remove)),
+        ]);
+        Keymap { bindings }
+    }
+
+    /// the built-in bindings with `~/.config/cli-roulette/keys.toml` on top,
+    /// every mistake and conflict in the file is reported at once
+    pub fn load() -> color_eyre::Result<Keymap> {
+        let mut keymap = Keymap::new();
+        let Some(path) = config_dir().map(|dir| dir.join(KEYS_FILE)) else {
+            return Ok(keymap);
+        };
+        if !path.exists() {
+            return Ok(keymap);
+        }
+
+        let text = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("could not read keys file {}", path.display()))?;
+        let file: BTreeMap<String, BTreeMap<String, KeyList>> = toml::from_str(&text)
+            .wrap_err_with(|| format!("could not parse keys file {}", path.display()))?;
+
+        let mut problems = keymap.rebind(file);
+        problems.extend(keymap.conflicts());
+        if !problems.is_empty() {
+            bail!("invalid key bindings in {}:\n  - {}", path.display(), problems.join("\n  - "));
+        }
+        Ok(keymap)
+    }
+
+    /// replaces the keys of every action listed in `file`, an empty list unbinds it
+    fn rebind(&mut self, file: BTreeMap<String, BTreeMap<String, KeyList>>) -> Vec<String> {
+        let defaults = Keymap::new();
+        let mut problems = Vec::new();
+        for (section, actions) in file {
+            let Some(context) = Context::ALL.into_iter().find(|context| context.name() == section) else {
+                let names: Vec<_> = Context::ALL.iter().map(|context| context.name()).collect();
+                problems.push(format!("unknown section [{}], expected one of {}", section, names.join(", ")));
+                continue;
+            };
+            for (name, keys) in actions {
+                //only what the context already does can be rebound there
+                let action = defaults.bindings.iter()
+                    .filter(|binding| binding.context == context)
+                    .map(|binding| binding.action)
+                    .find(|action| action.name() == name);
+                let Some(action) = action else {
+                    problems.push(format!("[{}] has no action `{}`", section, name));
+                    continue;
+                };
+                let keys = match keys {
+                    KeyList::One(key) => vec![key],
+                    KeyList::Many(keys) => keys,
+                };
+                let mut chords = Vec::new();
+                for key in keys {
+                    match key.parse::<KeyChord>() {
+                        Ok(chord) => chords.push(chord),
+                        Err(err) => problems.push(format!("[{}] {}: {}", section, name, err)),
+                    }
+                }
+                self.bindings.retain(|binding| binding.context != context || binding.action.name() != name);
+                self.bindings.extend(chords.into_iter().map(|chord| Binding { context, chord, action }));
+            }
+        }
+        problems
+    }
+
+    /// keys bound to two different actions where both could fire
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (idx, first) in self.bindings.iter().enumerate() {
+            for second in &self.bindings[idx + 1..] {
+                let clash = first.chord.same_key(&second.chord)
+                    && first.context.overlaps(second.context)
+                    && first.action.name() != second.action.name();
+                if clash {
+                    problems.push(format!(
+                        "{} is bound to `{}` in [{}] and to `{}` in [{}]",
+                        first.chord, first.action.name(), first.context.name(),
+                        second.action.name(), second.context.name()
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    /// the action for a key pressed while `contexts` are active, earlier contexts win
+    pub fn action(&self, contexts: &[Context], key_event: &KeyEvent) -> Option<Action> {
+        contexts.iter().find_map(|context| {
+            self.bindings
+                .iter()
+                .find(|binding| binding.context == *context && binding.chord.matches(key_event))
+                .map(|binding| binding.action)
+        })
+    }
+
+    /// the keys for the action called `name` in `context`, for hints like "space: shoot"
+    pub fn keys(&self, context: Context, name: &str) -> String {
+        let keys: Vec<String> = self.bindings
+            .iter()
+            .filter(|binding| binding.context == context && binding.action.name() == name)
+            .map(|binding| binding.chord.to_string())
+            .collect();
+        if keys.is_empty() {
+            "unbound".to_string()
+        } else {
+            keys.join("/")
+        }
+    }
+
+    /// the bindings of one context as (keys, description), keys doing the same thing share a line
+    pub fn describe(&self, context: Context) -> Vec<(String, &'static str)> {
+        let mut lines: Vec<(String, &'static str)> = Vec::new();
+        for binding in self.bindings.iter().filter(|binding| binding.context == context) {
+            let description = binding.action.describe();
+            match lines.iter_mut().find(|(_, existing)| *existing == description) {
+                Some((keys, _)) => keys.push_str(&format!("/{}", binding.chord)),
+                None => lines.push((binding.chord.to_string(), description)),
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(text: &str) -> KeyChord {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_modifiers_in_any_case() {
+        assert_eq!(chord("ctrl+q"), KeyChord::ctrl(KeyCode::Char('q')));
+        assert_eq!(chord("CTRL+Q"), KeyChord::ctrl(KeyCode::Char('q')));
+        assert_eq!(chord("Ctrl+Alt+x"), KeyChord {
+            code: KeyCode::Char('x'),
+            modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT,
+        });
+        assert_eq!(chord("shift+Up"), KeyChord { code: KeyCode::Up, modifiers: KeyModifiers::SHIFT });
+        assert_eq!(chord("shift+tab"), KeyChord { code: KeyCode::BackTab, modifiers: KeyModifiers::SHIFT });
+        assert_eq!(chord("PageDown"), KeyChord::key(KeyCode::PageDown));
+        assert_eq!(chord("F12"), KeyChord::key(KeyCode::F(12)));
+    }
+
+    #[test]
+    fn shift_is_part_of_a_character() {
+        assert_eq!(chord("shift+a"), KeyChord::key(KeyCode::Char('a')));
+        assert_eq!(chord("A"), KeyChord::key(KeyCode::Char('a')));
+        assert!(chord("a").same_key(&KeyChord { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT }));
+        assert!(!chord("a").same_key(&chord("ctrl+a")));
+    }
+
+    #[test]
+    fn plus_is_a_key_on_its_own() {
+        assert_eq!(chord("+"), KeyChord::key(KeyCode::Char('+')));
+        assert_eq!(chord("ctrl++"), KeyChord::ctrl(KeyCode::Char('+')));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!("hyper+x".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+        assert!("nope".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn display_parses_back_to_the_same_chord() {
+        for text in ["q", "space", "ctrl+tab", "shift+tab", "alt+shift+left", "ctrl+alt+f5", "home", "/", "?"] {
+            let parsed = chord(text);
+            assert_eq!(parsed.to_string(), text);
+            assert_eq!(chord(&parsed.to_string()), parsed);
+        }
+        for binding in Keymap::new().bindings {
+            assert_eq!(chord(&binding.chord.to_string()), binding.chord, "{}", binding.chord);
+        }
+    }
+
+    fn keymap(bindings: &[(Context, &str, Action)]) -> Keymap {
+        let bindings = bindings
+            .iter()
+            .map(|(context, key, action)| Binding { context: *context, chord: chord(key), action: *action })
+            .collect();
+        Keymap { bindings }
+    }
+
+    #[test]
+    fn default_bindings_have_no_conflicts() {
+        assert_eq!(Keymap::new().conflicts(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn slot_keys_stop_at_the_biggest_inventory() {
+        let slots: Vec<usize> = Keymap::new().bindings
+            .iter()
+            .filter_map(|binding| match binding.action {
+                Action::UseSlot(slot) => Some(slot),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(slots, (0..MAX_INVENTORY_SIZE).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn conflicts_only_between_overlapping_contexts() {
+        use Context as C;
+        //global keys clash with everything
+        assert_eq!(keymap(&[(C::Global, "x", Action::Quit), (C::Menu, "x", Action::Shoot)]).conflicts().len(), 1);
+        //the tutorial and sandbox tables are played on top of the table keys
+        assert_eq!(keymap(&[(C::Table, "x", Action::Shoot), (C::Tutorial, "X", Action::Reload)]).conflicts().len(), 1);
+        assert_eq!(keymap(&[(C::Sandbox, "x", Action::Reload), (C::Table, "x", Action::Shoot)]).conflicts().len(), 1);
+        //any focused window shares the window keys
+        assert_eq!(keymap(&[(C::Window, "m", Action::ShowLog), (C::Log, "m", Action::ShowData)]).conflicts().len(), 1);
+        //a focused window shadowing the table is on purpose
+        assert!(keymap(&[(C::Table, "j", Action::Shoot), (C::Log, "j", Action::ScrollDown)]).conflicts().is_empty());
+        assert!(keymap(&[(C::Menu, "x", Action::Quit), (C::Table, "x", Action::Shoot)]).conflicts().is_empty());
+    }
+
+    #[test]
+    fn same_action_twice_is_not_a_conflict() {
+        use Context as C;
+        assert!(keymap(&[(C::Global, "q", Action::Quit), (C::Table, "q", Action::Quit)]).conflicts().is_empty());
+        //but different modifiers are different keys
+        assert!(keymap(&[(C::Table, "tab", Action::FocusNext), (C::Table, "ctrl+tab", Action::FocusPrev)]).conflicts().is_empty());
+    }
+}
@@ -1,4 +1,9 @@
 use crate::app::App;
+use crate::cli::Args;
+use crate::keymap::Keymap;
+use crate::presets::Preset;
+use crate::rules::Rules;
+use crate::ui_components::logger::LogEntry;
 
 pub mod event;
 pub mod ui;
@@ -6,12 +11,44 @@ pub mod components;
 pub mod ui_components;
 pub mod data;
 pub mod app;
+pub mod config;
+pub mod cli;
+pub mod rules;
+pub mod presets;
+pub mod screens;
+pub mod keymap;
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse()?;
+    //checked before the terminal is taken over, so a bad file prints a readable error
+    let rules = Rules::load(args.rules.as_deref())?;
+    let mut all_presets = presets::builtin(&rules);
+    //a broken presets file only costs the saved presets, the built-in ones still work
+    let presets_problem = match presets::load_custom() {
+        Ok(custom) => {
+            all_presets.extend(custom);
+            None
+        },
+        Err(err) => Some(err),
+    };
+    let preset = match &args.preset {
+        Some(name) => presets::find(&all_presets, name)?.clone(),
+        None => all_presets[0].clone(),
+    };
+    //an explicit rules file wins over the rules bundled with the preset
+    let preset = match args.rules {
+        Some(_) => Preset { rules, ..preset },
+        None => preset,
+    };
+    let keymap = Keymap::load()?;
     let terminal = ratatui::init();
-    let result = App::new().run(terminal).await;
+    let mut app = App::new(args, all_presets, preset, keymap);
+    if let Some(err) = presets_problem {
+        app.log(LogEntry::system(format!("Playing with the built-in presets only, {:#}", err)).warning());
+    }
+    let result = app.run(terminal).await;
     ratatui::restore();
     result
 }
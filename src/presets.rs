@@ -0,0 +1,176 @@
+//presets.rs
+//named bundles of rules and table settings, the built-in ones plus whatever the player saved
+use std::collections::BTreeMap;
+use std::fs;
+
+use color_eyre::eyre::{bail, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::components::items::Items;
+use crate::components::shotgun::{Shell, ShotgunModel};
+use crate::config::config_dir;
+use crate::rules::Rules;
+
+pub const PRESETS_FILE: &str = "presets.toml";
+
+/// where the shells of every reload come from
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellSource {
+    /// `random_shells`, exotic shells included
+    #[default]
+    Random,
+    /// `default_shells`, Live and Blank only
+    Classic,
+}
+
+impl ShellSource {
+    pub const ALL: [ShellSource; 2] = [ShellSource::Random, ShellSource::Classic];
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Preset {
+    pub name: String,
+    pub rules: Rules,
+    pub shells: ShellSource,
+    pub model: ShotgunModel,
+    /// whether the message log can be opened
+    pub log: bool,
+    /// whether the odds panel for the next shell can be shown
+    pub odds: bool,
+    /// saved by the player rather than built in
+    #[serde(skip)]
+    pub custom: bool,
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Preset {
+            name: "Standard".to_string(),
+            rules: Rules::default(),
+            shells: ShellSource::Random,
+            model: ShotgunModel::Default,
+            log: true,
+            odds: true,
+            custom: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetFile {
+    #[serde(default)]
+    presets: Vec<Preset>,
+}
+
+/// The built-in presets. `Standard` plays with `rules`, the others are variations on it.
+pub fn builtin(rules: &Rules) -> Vec<Preset> {
+    let standard = Preset {
+        rules: rules.clone(),
+        ..Preset::default()
+    };
+
+    let classic = Preset {
+        name: "Classic".to_string(),
+        shells: ShellSource::Classic,
+        rules: Rules {
+            item_pool: BTreeMap::from([
+                (Items::Saw, 1),
+                (Items::Beer, 1),
+                (Items::Cigarette, 1),
+                (Items::MagnifyingGlass, 1),
+                (Items::Handcuffs, 1),
+            ]),
+            ..rules.clone()
+        },
+        ..standard.clone()
+    };
+
+    let chaos = Preset {
+        name: "Chaos".to_string(),
+        rules: Rules {
+            random_shells: Shell::ALL.iter().map(|shell| {
+                let weight = match shell {
+                    Shell::Live | Shell::Blank => 6,
+                    _ => 3,
+                };
+                (shell.clone(), weight)
+            }).collect(),
+            item_pool: Items::ALL.iter().map(|item| (*item, 1)).collect(),
+            ..rules.clone()
+        },
+        ..standard.clone()
+    };
+
+    let revolver = Preset {
+        name: "Revolver".to_string(),
+        model: ShotgunModel::Revolver,
+        ..standard.clone()
+    };
+
+    let hardcore = Preset {
+        name: "Hardcore".to_string(),
+        log: false,
+        odds: false,
+        rules: Rules {
+            starting_health: 1,
+            ..rules.clone()
+        },
+        ..standard.clone()
+    };
+
+    vec![standard, classic, chaos, revolver, hardcore]
+}
+
+/// presets the player saved, a broken file is reported instead of silently dropped
+pub fn load_custom() -> color_eyre::Result<Vec<Preset>> {
+    let Some(path) = config_dir().map(|dir| dir.join(PRESETS_FILE)) else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let text = fs::read_to_string(&path)
+        .wrap_err_with(|| format!("could not read presets file {}", path.display()))?;
+    let file: PresetFile = toml::from_str(&text)
+        .wrap_err_with(|| format!("could not parse presets file {}", path.display()))?;
+
+    let mut presets = file.presets;
+    for preset in presets.iter_mut() {
+        let problems = preset.rules.validate();
+        if !problems.is_empty() {
+            bail!(
+                "invalid rules in preset `{}` in {}:\n  - {}",
+                preset.name, path.display(), problems.join("\n  - ")
+            );
+        }
+        preset.custom = true;
+    }
+    Ok(presets)
+}
+
+/// writes every custom preset in `presets` to the config directory
+pub fn save_custom(presets: &[Preset]) -> color_eyre::Result<()> {
+    let dir = config_dir().ok_or_else(|| color_eyre::eyre::eyre!("no config directory"))?;
+    //the presets in a file that didn't load were never in `presets`, writing would lose them
+    load_custom().wrap_err("fix or remove the presets file before saving")?;
+    fs::create_dir_all(&dir)?;
+    let file = PresetFile {
+        presets: presets.iter().filter(|preset| preset.custom).cloned().collect(),
+    };
+    fs::write(dir.join(PRESETS_FILE), toml::to_string(&file)?)?;
+    Ok(())
+}
+
+/// finds a preset by name, ignoring case
+pub fn find<'a>(presets: &'a [Preset], name: &str) -> color_eyre::Result<&'a Preset> {
+    match presets.iter().find(|preset| preset.name.eq_ignore_ascii_case(name)) {
+        Some(preset) => Ok(preset),
+        None => {
+            let names: Vec<&str> = presets.iter().map(|preset| preset.name.as_str()).collect();
+            bail!("unknown preset `{}`, pick one of: {}", name, names.join(", "))
+        },
+    }
+}
@@ -0,0 +1,149 @@
+//rules.rs
+//the tunable numbers of the game, loaded from a toml file so they can change without recompiling
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::{bail, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::components::enums::ReloadAmount;
+use crate::components::items::Items;
+use crate::components::shotgun::Shell;
+use crate::config::config_dir;
+
+pub const RULES_FILE: &str = "rules.toml";
+
+/// anything above this can't be drawn on the status bar anyway
+pub const MAX_STARTING_HEALTH: u8 = 9;
+
+/// slots in a player's inventory
+pub const MAX_INVENTORY_SIZE: usize = 8;
+
+/// shells in one load, anything bigger turns a round into a slog
+pub const MAX_RELOAD_SHELLS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    pub starting_health: u8,
+    /// shells loaded for each `ReloadAmount`, from `One` to `Five`
+    pub reload_amounts: Vec<usize>,
+    /// weights used by `load_random_shells`
+    pub random_shells: BTreeMap<Shell, usize>,
+    /// weights used by `load_default_shells`, classic Live and Blank only
+    pub default_shells: BTreeMap<Shell, usize>,
+    /// items that can be handed out and how common each one is
+    pub item_pool: BTreeMap<Items, usize>,
+    /// items dealt to each player after every reload
+    pub items_per_reload: usize,
+    /// items a player can hold, anything dealt past this is lost
+    pub inventory_size: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            starting_health: 3,
+            reload_amounts: ReloadAmount::ALL.iter().map(ReloadAmount::as_usize).collect(),
+            random_shells: BTreeMap::from([
+                (Shell::Live, 10),
+                (Shell::Blank, 12),
+                (Shell::Poison, 1),
+                (Shell::BeanBag, 2),
+                (Shell::Taser, 1),
+                (Shell::Imposter, 1),
+            ]),
+            default_shells: BTreeMap::from([
+                (Shell::Live, 10),
+                (Shell::Blank, 14),
+            ]),
+            item_pool: BTreeMap::from([
+                (Items::Saw, 6),
+                (Items::Beer, 8),
+                (Items::Cigarette, 6),
+                (Items::MagnifyingGlass, 6),
+                (Items::Handcuffs, 4),
+                (Items::Inverter, 3),
+                (Items::Mirror, 2),
+                (Items::Meth, 2),
+                (Items::Adrenaline, 2),
+                (Items::LSD, 2),
+                (Items::AED, 1),
+            ]),
+            items_per_reload: 2,
+            inventory_size: MAX_INVENTORY_SIZE,
+        }
+    }
+}
+
+impl Rules {
+    /// reads the rules from `path`, or from the config directory when no path is given.
+    /// Without a file the built-in rules are used, a file that is there but wrong is an error.
+    pub fn load(path: Option<&Path>) -> color_eyre::Result<Rules> {
+        let path: PathBuf = match path {
+            Some(path) => path.to_path_buf(),
+            None => match config_dir().map(|dir| dir.join(RULES_FILE)) {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Rules::default()),
+            },
+        };
+
+        let text = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("could not read rules file {}", path.display()))?;
+        let rules: Rules = toml::from_str(&text)
+            .wrap_err_with(|| format!("could not parse rules file {}", path.display()))?;
+
+        let problems = rules.validate();
+        if !problems.is_empty() {
+            bail!("invalid rules in {}:\n  - {}", path.display(), problems.join("\n  - "));
+        }
+        Ok(rules)
+    }
+
+    /// everything wrong with the rules, empty when they are fine
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if !(1..=MAX_STARTING_HEALTH).contains(&self.starting_health) {
+            problems.push(format!("starting_health must be between 1 and {}", MAX_STARTING_HEALTH));
+        }
+
+        if self.reload_amounts.len() != ReloadAmount::ALL.len() {
+            problems.push(format!(
+                "reload_amounts needs exactly {} entries, one per reload size, found {}",
+                ReloadAmount::ALL.len(), self.reload_amounts.len()
+            ));
+        }
+        if self.reload_amounts.contains(&0) {
+            problems.push("reload_amounts can't contain 0".to_string());
+        }
+        if self.reload_amounts.iter().any(|amount| *amount > MAX_RELOAD_SHELLS) {
+            problems.push(format!("reload_amounts can be at most {} shells each", MAX_RELOAD_SHELLS));
+        }
+
+        for (name, weights) in [("random_shells", &self.random_shells), ("default_shells", &self.default_shells)] {
+            if weights.values().all(|weight| *weight == 0) {
+                problems.push(format!("{} needs at least one shell with a weight above 0", name));
+            }
+        }
+
+        if !self.item_pool.is_empty() && self.item_pool.values().all(|weight| *weight == 0) {
+            problems.push("item_pool needs at least one item with a weight above 0, or no items at all".to_string());
+        }
+
+        if self.inventory_size > MAX_INVENTORY_SIZE {
+            problems.push(format!("inventory_size can be at most {}", MAX_INVENTORY_SIZE));
+        }
+
+        problems
+    }
+
+    /// shells loaded for a reload size
+    pub fn reload_size(&self, amount: ReloadAmount) -> usize {
+        self.reload_amounts
+            .get(amount.index())
+            .copied()
+            .unwrap_or(amount.as_usize())
+    }
+}
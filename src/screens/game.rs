@@ -0,0 +1,52 @@
+//game.rs
+//the table itself, where the shooting happens
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders},
+    Frame,
+};
+
+use crate::app::App;
+use crate::ui;
+
+pub fn render(app: &App, frame: &mut Frame) -> Option<String> {
+    let border = Block::default()
+        .title("Main UI - Press 'd' for data, 'l' for log, '?' for help")
+        .border_style(Style::default().fg(Color::Red))
+        .border_type(BorderType::Rounded)
+        .borders(Borders::ALL);
+
+    frame.render_widget(&border, frame.area());
+
+    //this should happen after the round is begun
+    //render inventory
+    //render player
+    //render shotgun
+    ui::render_shotgun_popup(app, frame);
+    ui::render_status_bar(app, frame);
+    ui::render_known_shells(app, frame);
+    if app.show_odds && app.preset.odds {
+        ui::render_odds_panel(app, frame);
+    }
+    if app.show_tracker {
+        ui::render_spent_tracker(app, frame);
+    }
+
+    // does it in order of the "stack"
+    for kind in &app.widget_data.render_stack {
+        if let Some(pane) = app.widget_data.pane(*kind).filter(|_| app.widget_data.is_displayed(*kind)) {
+            ui::render_pane(app, frame, pane);
+        }
+    }
+
+    if app.loot.is_some() {
+        ui::render_loot_popup(app, frame);
+    }
+    if app.tutorial.is_some() {
+        ui::render_tutorial_popup(app, frame);
+    }
+    if app.sandbox.is_some() {
+        ui::render_sandbox_panel(app, frame);
+    }
+    None
+}
@@ -0,0 +1,51 @@
+//game_over.rs
+//after a death or a cash out, with the result and the high scores
+use ratatui::{prelude::*, Frame};
+
+use crate::app::App;
+use crate::screens::setup::GameMode;
+use super::{menu_area, render_menu, step_cursor, MenuInput, Screen};
+
+const OPTIONS: [&str; 3] = ["Play again", "Title", "Quit"];
+
+pub fn update(app: &mut App, input: MenuInput) {
+    let Screen::GameOver { cursor, .. } = &mut app.screen else {
+        return;
+    };
+    match input {
+        MenuInput::Select => match *cursor {
+            //from scratch, so nothing the last game set carries over
+            0 => {
+                let preset = app.preset.clone();
+                app.start_game(app.mode, preset);
+            },
+            1 => {
+                app.abandon_game();
+                app.screen = Screen::Title { cursor: 0 };
+            },
+            _ => app.quit(),
+        },
+        MenuInput::Back => {
+            app.abandon_game();
+            app.screen = Screen::Title { cursor: 0 };
+        },
+        _ => *cursor = step_cursor(*cursor, OPTIONS.len(), input),
+    }
+}
+
+pub fn render(app: &App, frame: &mut Frame, lines: &[String], cursor: usize) {
+    let mut header: Vec<Line> = lines.iter().map(|line| Line::from(line.as_str())).collect();
+    header.push(Line::from(""));
+    //a daily's result is in the lines already, the high scores are for regular runs
+    if app.mode == GameMode::Match {
+        header.push(Line::from("High scores"));
+        if app.high_scores.entries.is_empty() {
+            header.push(Line::from("  none yet, cash out a run to get on the board"));
+        }
+        for (rank, entry) in app.high_scores.entries.iter().enumerate() {
+            header.push(Line::from(format!("{:>2}. {:>8}  stage {}", rank + 1, entry.score, entry.stage)));
+        }
+        header.push(Line::from(""));
+    }
+    render_menu(frame, menu_area(frame), "Game over", header, &OPTIONS, cursor);
+}
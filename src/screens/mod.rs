@@ -0,0 +1,183 @@
+//screens
+//the top-level state machine, every screen maps its own keys and draws itself
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::event::AppEvent;
+use crate::keymap::Context;
+use crate::ui::centered_rect;
+use crate::ui_components::widget_data::Pane;
+
+pub mod title;
+pub mod setup;
+pub mod settings;
+pub mod game;
+pub mod paused;
+pub mod summary;
+pub mod game_over;
+
+use setup::Setup;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Screen {
+    /// the menu shown on start
+    Title { cursor: usize },
+    /// picking a mode and a preset for a new game
+    Setup(Setup),
+    /// the preset picker, `from_game` decides where closing it goes back to
+    Settings { from_game: bool },
+    /// at the table
+    Game,
+    /// the table, frozen under the pause menu
+    Paused { cursor: usize },
+    /// a match was won, double or nothing?
+    Summary,
+    /// the player died or cashed out
+    GameOver { lines: Vec<String>, cursor: usize },
+}
+
+/// keys the menu screens react to
+#[derive(Debug, Clone, Copy)]
+pub enum MenuInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+}
+
+/// the event for a key on the current screen, `None` if it does nothing there
+pub fn handle_key(app: &App, key_event: KeyEvent) -> Option<AppEvent> {
+    //the help overlay takes the keys while it is open
+    if app.help.open {
+        return app.keymap.action(&[Context::Global, Context::Help], &key_event).map(|action| action.event(app));
+    }
+    let contexts = contexts(app);
+    let Some(pane) = focused_pane(app) else {
+        return app.keymap.action(&contexts, &key_event).map(|action| action.event(app));
+    };
+
+    //the focused pane answers after the keys that work anywhere, ahead of the window's and the table's
+    let split = contexts.iter().position(|context| *context == Context::Window).unwrap_or(contexts.len());
+    let (ahead, behind) = contexts.split_at(split);
+    let ahead: Vec<Context> = ahead.iter().copied().filter(|context| Some(*context) != pane.context()).collect();
+    app.keymap.action(&ahead, &key_event).map(|action| action.event(app))
+        .or_else(|| pane.handle(app, &key_event))
+        .or_else(|| app.keymap.action(behind, &key_event).map(|action| action.event(app)))
+}
+
+/// the pane with focus at the table, its keys come before the table's
+fn focused_pane(app: &App) -> Option<&dyn Pane> {
+    if app.screen != Screen::Game {
+        return None;
+    }
+    app.widget_data.shown_widgets().and_then(|kind| app.widget_data.pane(kind))
+}
+
+/// the keymap contexts of the current screen, the first one with a binding for a key wins
+pub fn contexts(app: &App) -> Vec<Context> {
+    let mut contexts = vec![Context::Global];
+    match &app.screen {
+        Screen::Title { .. } | Screen::Setup(_) | Screen::Paused { .. } | Screen::GameOver { .. } => {
+            contexts.push(Context::Menu);
+        },
+        Screen::Settings { .. } => contexts.push(Context::Settings),
+        Screen::Summary => contexts.push(Context::Confirmation),
+        Screen::Game => {
+            if app.tutorial.is_some() {
+                contexts.push(Context::Tutorial);
+            }
+            //a focused pane's keys win over the table's
+            if let Some(pane) = focused_pane(app) {
+                contexts.extend(pane.context());
+                contexts.push(Context::Window);
+            }
+            if app.sandbox.is_some() {
+                contexts.push(Context::Sandbox);
+            }
+            contexts.push(Context::Table);
+        },
+    }
+    contexts
+}
+
+/// moves a menu screen along, `Select` and `Back` are up to the screen
+pub fn update(app: &mut App, input: MenuInput) {
+    match &app.screen {
+        Screen::Title { .. } => title::update(app, input),
+        Screen::Setup(_) => setup::update(app, input),
+        Screen::Paused { .. } => paused::update(app, input),
+        Screen::GameOver { .. } => game_over::update(app, input),
+        Screen::Settings { .. } | Screen::Game | Screen::Summary => {},
+    }
+}
+
+/// draws the current screen, the table is kept underneath the screens that pause it
+pub fn render(app: &App, frame: &mut Frame) -> Option<String> {
+    match &app.screen {
+        Screen::Title { cursor } => title::render(frame, *cursor),
+        Screen::Setup(setup) => setup::render(app, frame, setup),
+        Screen::Settings { .. } => settings::render(app, frame),
+        Screen::Game => return game::render(app, frame),
+        Screen::Paused { cursor } => {
+            let log = game::render(app, frame);
+            paused::render(frame, *cursor);
+            return log;
+        },
+        Screen::Summary => {
+            let log = game::render(app, frame);
+            summary::render(app, frame);
+            return log;
+        },
+        Screen::GameOver { lines, cursor } => game_over::render(app, frame, lines, *cursor),
+    }
+    None
+}
+
+/// moves a list cursor, wrapping around at both ends
+fn step_cursor(cursor: usize, len: usize, input: MenuInput) -> usize {
+    match input {
+        MenuInput::Up => (cursor + len - 1) % len,
+        MenuInput::Down => (cursor + 1) % len,
+        _ => cursor,
+    }
+}
+
+/// a boxed list of options with the cursor on one, `header` goes above them
+fn render_menu(frame: &mut Frame, area: Rect, title: &str, header: Vec<Line>, options: &[&str], cursor: usize) {
+    let mut lines = header;
+    for (idx, option) in options.iter().enumerate() {
+        if idx == cursor {
+            lines.push(Line::from(Span::styled(format!("> {}", option), Style::default().fg(Color::LightRed))));
+        } else {
+            lines.push(Line::from(format!("  {}", option)));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "arrows or j/k to move, enter to pick, esc to go back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let menu = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(menu, area);
+}
+
+/// the usual spot for a menu box
+fn menu_area(frame: &Frame) -> Rect {
+    centered_rect(40, 50, frame.area())
+}
@@ -0,0 +1,38 @@
+//paused.rs
+//the pause menu, the dealer waits while it is up
+use ratatui::{prelude::*, Frame};
+
+use crate::app::App;
+use crate::event::AppEvent;
+use crate::ui_components::dialog::Dialog;
+use super::{menu_area, render_menu, step_cursor, MenuInput, Screen};
+
+const OPTIONS: [&str; 4] = ["Resume", "Settings", "Quit to title", "Quit"];
+
+pub fn update(app: &mut App, input: MenuInput) {
+    let Screen::Paused { cursor } = &mut app.screen else {
+        return;
+    };
+    match input {
+        MenuInput::Select => match *cursor {
+            0 => app.screen = Screen::Game,
+            //through the event, so the daily can say no
+            1 => app.events.send(AppEvent::ToggleSettings),
+            2 => {
+                let body = "Abandon this run? Nothing is recorded.".to_string();
+                app.dialogs.push(Dialog::confirm("Quit to title", body, AppEvent::QuitToTitle, None));
+            },
+            _ => app.quit(),
+        },
+        MenuInput::Back => app.screen = Screen::Game,
+        _ => *cursor = step_cursor(*cursor, OPTIONS.len(), input),
+    }
+}
+
+pub fn render(frame: &mut Frame, cursor: usize) {
+    let header = vec![
+        Line::from("Quitting to the title abandons the run, nothing is recorded."),
+        Line::from(""),
+    ];
+    render_menu(frame, menu_area(frame), "Paused", header, &OPTIONS, cursor);
+}
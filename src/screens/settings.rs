@@ -0,0 +1,64 @@
+//settings.rs
+//the preset picker, reachable from the title and from the pause menu or `o` during a game
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::ui::centered_rect;
+use crate::ui_components::settings::SettingsRow;
+
+//preset list on top, the editable draft below it
+pub fn render(app: &App, frame: &mut Frame) {
+    let area = centered_rect(50, 60, frame.area());
+    let settings = &app.settings;
+    let highlight = Style::default().fg(Color::LightRed);
+
+    let mut lines = vec![Line::from("Presets")];
+    for (idx, preset) in app.presets.iter().enumerate() {
+        let playing = if preset.name == app.preset.name { " (playing)" } else { "" };
+        let saved = if preset.custom { " [saved]" } else { "" };
+        let line = format!("{}{}{}", preset.name, saved, playing);
+        if idx == settings.cursor {
+            lines.push(Line::from(Span::styled(format!("> {}", line), highlight)));
+        } else {
+            lines.push(Line::from(format!("  {}", line)));
+        }
+    }
+
+    let draft = &settings.draft;
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("Draft, based on {}", draft.name)));
+    for row in SettingsRow::ALL {
+        let value = match row {
+            SettingsRow::Health => format!("< {} >", draft.rules.starting_health),
+            SettingsRow::Shells => format!("< {:?} >", draft.shells),
+            SettingsRow::Model => format!("< {:?} >", draft.model),
+            SettingsRow::Log => format!("< {} >", if draft.log { "on" } else { "off" }),
+            SettingsRow::Odds => format!("< {} >", if draft.odds { "on" } else { "off" }),
+        };
+        let line = format!("{:<7} {}", format!("{:?}", row), value);
+        if settings.selected_row(&app.presets) == Some(row) {
+            lines.push(Line::from(Span::styled(format!("> {}", line), highlight)));
+        } else {
+            lines.push(Line::from(format!("  {}", line)));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "enter: play  left/right: edit draft  w: save draft  o or esc: back",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Settings - a new match starts with the preset you pick")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded))
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
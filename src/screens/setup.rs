@@ -0,0 +1,88 @@
+//setup.rs
+//new game setup: what to play and with which preset
+use ratatui::{prelude::*, Frame};
+
+use crate::app::App;
+use crate::presets::Preset;
+use super::{menu_area, render_menu, step_cursor, MenuInput, Screen};
+
+/// the ways to play, same as the command line flags
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    Match,
+    Daily,
+    Tutorial,
+    Sandbox,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [GameMode::Match, GameMode::Daily, GameMode::Tutorial, GameMode::Sandbox];
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            GameMode::Match => "endless matches against the dealer, double or nothing after every win",
+            GameMode::Daily => "today's seeded run, always with the Standard preset",
+            GameMode::Tutorial => "a rigged game that explains every shell and item",
+            GameMode::Sandbox => "set the shells, items and health by hand",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setup {
+    /// 0 is the mode, 1 the preset, 2 start
+    pub row: usize,
+    pub mode: usize,
+    pub preset: usize,
+}
+
+const ROWS: usize = 3;
+
+impl Setup {
+    /// starts on the preset being played
+    pub fn new(presets: &[Preset], current: &Preset) -> Self {
+        let preset = presets.iter().position(|preset| preset.name == current.name).unwrap_or(0);
+        Setup { row: 0, mode: 0, preset }
+    }
+}
+
+pub fn update(app: &mut App, input: MenuInput) {
+    let Screen::Setup(setup) = &mut app.screen else {
+        return;
+    };
+    let cycle = |idx: usize, len: usize, forward: bool| if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+    match input {
+        MenuInput::Left | MenuInput::Right => {
+            let forward = matches!(input, MenuInput::Right);
+            match setup.row {
+                0 => setup.mode = cycle(setup.mode, GameMode::ALL.len(), forward),
+                1 => setup.preset = cycle(setup.preset, app.presets.len(), forward),
+                _ => {},
+            }
+        },
+        MenuInput::Select => {
+            let mode = GameMode::ALL[setup.mode];
+            let preset = app.presets[setup.preset].clone();
+            app.start_game(mode, preset);
+        },
+        MenuInput::Back => app.screen = Screen::Title { cursor: 0 },
+        _ => setup.row = step_cursor(setup.row, ROWS, input),
+    }
+}
+
+pub fn render(app: &App, frame: &mut Frame, setup: &Setup) {
+    let mode = GameMode::ALL[setup.mode];
+    let preset = &app.presets[setup.preset];
+    let options = [
+        format!("Mode    < {:?} >", mode),
+        format!("Preset  < {} >", preset.name),
+        "Start".to_string(),
+    ];
+    let options: Vec<&str> = options.iter().map(String::as_str).collect();
+    let header = vec![
+        Line::from(mode.describe()),
+        Line::from(Span::styled("left and right change the mode and preset", Style::default().fg(Color::DarkGray))),
+        Line::from(""),
+    ];
+    render_menu(frame, menu_area(frame), "New game", header, &options, setup.row);
+}
@@ -0,0 +1,55 @@
+//summary.rs
+//a match was won: the run so far, and double or nothing
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::App;
+use crate::ui::centered_rect;
+use crate::ui_components::colors::{to_color, Colors};
+
+pub fn render(app: &App, frame: &mut Frame) {
+    let colors = Colors::default();
+    let area = centered_rect(40, 50, frame.area());
+
+    let mut lines = Vec::new();
+    if let Some(run) = &app.endless {
+        lines.push(Line::from(format!("Stage {} cleared, run score: {}", run.stage, run.score)));
+        lines.push(Line::from(format!(
+            "Continue to stage {} at x{}? Dying loses everything.", run.stage + 1, run.multiplier * 2
+        )));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("[y] double or nothing", Style::default().fg(to_color(colors.active))),
+        Span::raw("   "),
+        Span::raw("[n] cash out"),
+    ]));
+    //a daily cash out is kept with the daily result, not on the endless board
+    if app.daily.is_none() {
+        lines.push(Line::from(""));
+        lines.push(Line::from("High scores"));
+        for (rank, entry) in app.high_scores.entries.iter().enumerate() {
+            lines.push(Line::from(format!("{:>2}. {:>8}  stage {}", rank + 1, entry.score, entry.stage)));
+        }
+    }
+
+    let title = match &app.daily {
+        Some(daily) => format!("Daily {} - double or nothing", daily.challenge.date),
+        None => "Double or nothing".to_string(),
+    };
+    let popup = Paragraph::new(lines)
+        .block(Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(to_color(colors.secondary))))
+        .wrap(Wrap { trim: true })
+        .style(Style::default()
+            .fg(to_color(colors.primary))
+            .bg(to_color(colors.background)));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
@@ -0,0 +1,34 @@
+//title.rs
+//the first thing on screen when no mode was picked on the command line
+use ratatui::{prelude::*, Frame};
+
+use crate::app::App;
+use crate::ui::SHOTGUN_ART;
+use super::{menu_area, render_menu, setup::Setup, step_cursor, MenuInput, Screen};
+
+const OPTIONS: [&str; 3] = ["New game", "Settings", "Quit"];
+
+pub fn update(app: &mut App, input: MenuInput) {
+    let Screen::Title { cursor } = &mut app.screen else {
+        return;
+    };
+    match input {
+        MenuInput::Select => match *cursor {
+            0 => app.screen = Screen::Setup(Setup::new(&app.presets, &app.preset)),
+            1 => app.screen = Screen::Settings { from_game: false },
+            _ => app.quit(),
+        },
+        MenuInput::Back => app.quit(),
+        _ => *cursor = step_cursor(*cursor, OPTIONS.len(), input),
+    }
+}
+
+pub fn render(frame: &mut Frame, cursor: usize) {
+    let mut header: Vec<Line> = SHOTGUN_ART.lines().map(Line::from).collect();
+    header.push(Line::from(Span::styled("C L I   R O U L E T T E", Style::default().fg(Color::Red).bold())));
+    header.push(Line::from(""));
+
+    let area = menu_area(frame);
+    let area = Rect { width: area.width.max(72).min(frame.area().width), ..area };
+    render_menu(frame, area, "cli-roulette", header, &OPTIONS, cursor);
+}
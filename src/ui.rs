@@ -1,16 +1,27 @@
-use std::rc::Rc;
-
 use ratatui::{
-    buffer::Buffer, symbols, layout::{Alignment, Rect}, prelude::*, style::{Color, Styled, Stylize}, widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap, canvas::Canvas, canvas::Line}
+    layout::Rect, prelude::*, style::{Color, Styled}, widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap}
 };
 
 //add svg crate
 /* use svg::{Tree, NodeKind}; */
 
-use crate::ui_components::widget_data::{WidgetKind, WidgetData};
+use crate::ui_components::widget_data::{Pane, WidgetKind};
+use crate::ui_components::ui_help::{self, HelpPage};
+use crate::ui_components::hit_map::HitTarget;
+use crate::ui_components::windows;
+use crate::ui_components::dialog::{Dialog, DialogKind};
 use crate::app::{ App };
-
-const PLAYER_ART: &str = r#"
+use crate::screens;
+use crate::keymap::Context;
+use crate::data::{PLAYER, DEALER};
+use crate::components::sandbox::SandboxRow;
+use crate::components::items::Rarity;
+use crate::components::player::Player;
+use crate::components::status::StatusKind;
+use crate::components::odds;
+use crate::components::shotgun::Shell;
+
+pub const PLAYER_ART: &str = r#"
  (\_/)
  ( •_•)
 />🍪
@@ -49,7 +60,7 @@ pub const SHELL: &str = r#"
 ━━┛┛ ┛━━┛━━┛━━┛
 "#;
 
-fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -70,174 +81,569 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 }
 
 pub fn render_ui(app: &App, frame: &mut Frame) -> Option<String> {
-    //will eventually get rid of this
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        // .margin(2)
-        .constraints([
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Percentage(18),
-            Constraint::Min(0),
-        ])
-        .split(frame.area());
+    let log = screens::render(app, frame);
 
-    let border = Block::default()
-        .title("Main UI - Press 'd' for data, 'l' for log")
-        .border_style(Style::default().fg(Color::Red))
-        .border_type(BorderType::Rounded)
+    //the overlays work on every screen
+    if app.help.open {
+        render_help(app, frame);
+    }
+    if app.palette.open {
+        render_palette(app, frame);
+    }
+    if app.console.open {
+        render_console(app, frame);
+    }
+    //dialogs wait for an answer, so they go over everything
+    for dialog in app.dialogs.iter() {
+        render_dialog(dialog, frame);
+    }
+    log
+}
+
+/// draws a shown pane where it was moved to, kept inside the terminal, or where it opens
+pub fn render_pane(app: &App, frame: &mut Frame, pane: &dyn Pane) {
+    let bounds = frame.area();
+    let area = app.widget_data
+        .get_state(pane.kind())
+        .area()
+        .map_or_else(|| pane.default_area(bounds), |area| windows::clamp(area, bounds));
+
+    frame.render_widget(Clear, area);
+    //before the pane draws, so whatever it registers inside sits on top
+    app.hit_map.borrow_mut().add(area, HitTarget::Widget(pane.kind()));
+    pane.render(app, frame, area);
+}
+
+/// the titled frame around a pane, red while it has focus
+pub fn pane_block(app: &App, pane: &dyn Pane) -> Block<'static> {
+    let mut block = Block::default()
+        .title(pane.title(app))
         .borders(Borders::ALL);
+    if app.widget_data.is_focused(pane.kind()) {
+        block = block.border_style(Style::default().fg(Color::LightRed));
+    }
+    block
+}
 
-    frame.render_widget(&border, frame.area());
-
-    //this should happen after the round is begun
-    //render inventory
-    //render player
-    //render shotgun
-    render_shotgun_popup(app, frame);
-
-    // does it in order of the "stack"
-    for kind in &app.widget_data.render_stack {
-        let state = app.widget_data.get_state(*kind);
-        if state.display {
-            match kind {
-                WidgetKind::Data => render_data_popup(app, frame),
-                WidgetKind::Log => render_log_popup(app, frame),
-                WidgetKind::Inventory => render_inventory_popup(app, frame, &chunks),
-                WidgetKind::Player => render_player_popup(app, frame),
-                _ => return Some("shotgun is already displayed by default".to_string()),
-            }
-        }
+//one tag per effect, like [PSN x2 1t]
+pub fn status_spans(player: &Player) -> Vec<Span<'static>> {
+    player.statuses()
+        .iter()
+        .map(|status| {
+            let color = match status.kind {
+                StatusKind::Stunned | StatusKind::Cuffed => Color::Yellow,
+                StatusKind::Poisoned => Color::Green,
+                StatusKind::Blind => Color::DarkGray,
+                StatusKind::Aed | StatusKind::Mirrored => Color::Cyan,
+            };
+            Span::styled(format!("[{}]", status.label()), Style::default().fg(color))
+        })
+        .collect()
+}
+
+pub fn rarity_color(rarity: Rarity) -> Color {
+    match rarity {
+        Rarity::Common => Color::White,
+        Rarity::Uncommon => Color::Cyan,
+        Rarity::Rare => Color::Magenta,
+    }
+}
+
+//begin changing "popups" to not be such as shotgun and inventory
+pub fn render_shotgun_popup(app: &App, frame: &mut Frame) {
+    let frame_area = frame.area();
+    let w = 68;
+    let h = 10;
+    let w = w.min(frame_area.width);
+    let h = h.min(frame_area.height);
+
+    let x = frame_area.x + (frame_area.width - w) / 2;
+    let y = frame_area.y + (frame_area.height - h) / 2;
+
+    let state = app.widget_data.get_state(WidgetKind::Shotgun);
+    let area = state.area().unwrap_or(Rect { x, y, width: w, height: h });
+    let mut shotgun_popup = Paragraph::new(state.content().unwrap_or(SHOTGUN_ART))
+        .block(Block::default().borders(Borders::empty()));
+
+    if app.widget_data.is_focused(WidgetKind::Shotgun) {
+        shotgun_popup = shotgun_popup.set_style(Style::default().fg(Color::LightRed))
     }
-    return None
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(shotgun_popup, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Shotgun);
 }
 
-fn render_data_popup(app: &App, frame: &mut Frame) {
-    let term_area = frame.area();
-    let term_width = term_area.width;
-    let term_height = term_area.height;
 
-    let width = term_width * 30 / 100;
-    let height = term_height * 30 / 100;
+//one line at the bottom of the table with everyone's health and the run score
+pub fn render_status_bar(app: &App, frame: &mut Frame) {
+    let frame_area = frame.area();
+    if frame_area.height < 3 {
+        return;
+    }
     let area = Rect {
-        x: 1,
-        y: 2,
-        width,
-        height,
+        x: frame_area.x + 1,
+        y: frame_area.bottom() - 2,
+        width: frame_area.width.saturating_sub(2),
+        height: 1,
     };
 
-    let popup_content = format!(
-        "Data: {:?} Counter: {}\nWindow: {:?}", app.data, app.match_data.round_count, app.logger.get_window(),
-    );
+    let mut spans: Vec<Span> = Vec::new();
+    let width = |spans: &[Span]| spans.iter().map(Span::width).sum::<usize>() as u16;
+    for (idx, player) in app.data.players.iter().enumerate() {
+        let start = width(&spans);
+        //the player a click on the shotgun fires at
+        let name_style = if idx == app.aim {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default()
+        };
+        spans.push(Span::styled(player.name().to_string(), name_style));
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled("♥".repeat(player.health() as usize), Style::default().fg(Color::Red)));
+        spans.push(Span::raw(" "));
+        spans.extend(status_spans(player));
+        let player_area = Rect { x: area.x + start, width: width(&spans) - start, ..area };
+        app.hit_map.borrow_mut().add(player_area.intersection(area), HitTarget::Player(idx));
+        spans.push(Span::raw(" "));
+    }
+    let turn = match app.data.match_data.turn {
+        Some(DEALER) => "dealer's turn".to_string(),
+        Some(_) => format!(
+            "your turn - {}: shoot dealer, {}: shoot yourself",
+            app.keymap.keys(Context::Table, "shoot"), app.keymap.keys(Context::Table, "shoot_self")
+        ),
+        None => "match over".to_string(),
+    };
+    spans.push(Span::raw(format!("| {} ", turn)));
+    spans.push(Span::styled(format!("| {} ", app.preset.name), Style::default().fg(Color::Magenta)));
+    if let Some(daily) = &app.daily {
+        let kind = if daily.scored { "daily" } else { "daily practice" };
+        spans.push(Span::styled(
+            format!("| {} {} ", kind, daily.challenge.date),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if let Some(run) = &app.endless {
+        spans.push(Span::styled(
+            format!("| stage {} score {} x{}", run.stage, run.score, run.multiplier),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
 
-    let mut data_popup = Paragraph::new(popup_content)
-        .block(Block::default().title("Popup").borders(Borders::ALL))
-        .wrap(Wrap { trim: true })
-        .style(Style::default()
-            .fg(app.widget_data.get_color(&WidgetKind::Data).unwrap_or(Color::White))
-        );
-    if app.widget_data.is_focused(WidgetKind::Data) {
-        data_popup = data_popup.set_style(Style::default().fg(Color::LightRed));
+//chances for the next shell as the player can work them out, docked top left
+pub fn render_odds_panel(app: &App, frame: &mut Frame) {
+    let odds = odds::next_shell_odds(&app.data.shotgun, app.data.players[PLAYER].knowledge());
+    if odds.is_empty() {
+        return;
     }
 
+    let frame_area = frame.area();
+    let bar_width = 12;
+    let area = Rect {
+        x: frame_area.x + 1,
+        y: frame_area.y + 1,
+        width: 32,
+        height: odds.len() as u16 + 2,
+    }.intersection(frame_area);
+
+    let lines: Vec<Line> = odds.iter()
+        .map(|(shell, chance)| {
+            let filled = (chance * bar_width as f64).round() as usize;
+            let color = match shell {
+                Shell::Live => Color::Red,
+                Shell::Blank => Color::Gray,
+                _ => Color::Magenta,
+            };
+            Line::from(vec![
+                Span::raw(format!("{:<12} {:>3.0}% ", format!("{:?}", shell), chance * 100.0)),
+                Span::styled("█".repeat(filled), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+
+    let panel = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Next shell - h to hide")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded));
+
     frame.render_widget(Clear, area);
-    frame.render_widget(data_popup, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Overlay);
+    frame.render_widget(panel, area);
 }
 
-fn render_log_popup(app: &App, frame: &mut Frame) {
-    let area = frame.area();
-    let width = (area.width as f32 * 0.33) as u16;
-    let height = (area.height as f32 * 0.75) as u16;
-    let x = (area.width.saturating_sub(width)) / 2;
-    let y = (area.height.saturating_sub(height)) / 2;
+//shells fired this load and what can still be in there, docked top right
+pub fn render_spent_tracker(app: &App, frame: &mut Frame) {
+    let shotgun = &app.data.shotgun;
+    let spent = shotgun.spent.borrow();
+    if spent.is_empty() && shotgun.is_empty() {
+        return;
+    }
 
+    let frame_area = frame.area();
+    //the sandbox panel has the top right corner
+    let top = if app.sandbox.is_some() { 17 } else { 1 };
+    let width = 34.min(frame_area.width.saturating_sub(2));
+    let rows = (frame_area.height / 3).max(1) as usize;
+    let shown = spent.len().min(rows);
+    //the borders, and the line with what is left
+    let extra = if app.preset.odds { 3 } else { 2 };
     let area = Rect {
-        x,
-        y,
+        x: frame_area.right().saturating_sub(width + 1),
+        y: frame_area.y + top,
+        width,
+        height: shown.max(1) as u16 + extra,
+    }.intersection(frame_area);
+
+    let names = |idx: usize| app.data.players[idx].name();
+    let mut lines: Vec<Line> = spent.iter()
+        .enumerate()
+        .skip(spent.len() - shown)
+        .map(|(idx, spent)| {
+            let who = match spent.at {
+                Some(at) => format!("{} > {}", names(spent.by), names(at)),
+                None => format!("{} ejected", names(spent.by)),
+            };
+            let color = match spent.shell {
+                Shell::Live => Color::Red,
+                Shell::Blank => Color::Gray,
+                _ => Color::Magenta,
+            };
+            Line::from(vec![
+                Span::raw(format!("{:>2}. {:<17}", idx + 1, who)),
+                Span::styled(format!("{:?}", spent.shell), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if spent.is_empty() {
+        lines.push(Line::from(Span::styled("nothing fired yet", Style::default().fg(Color::DarkGray))));
+    }
+
+    //what is left is the odds panel's job, a preset without odds doesn't get it here either
+    if app.preset.odds {
+        let left = odds::remaining(shotgun)
+            .iter()
+            .map(|(shell, count)| format!("{} {:?}", count, shell))
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(Line::from(format!("left: {}", if left.is_empty() { "nothing".to_string() } else { left })));
+    }
+
+    let panel = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Spent shells - t to hide")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded));
+
+    frame.render_widget(Clear, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Overlay);
+    frame.render_widget(panel, area);
+}
+
+//what the player has seen in the chamber, just above the status bar
+pub fn render_known_shells(app: &App, frame: &mut Frame) {
+    let known = app.data.players[PLAYER].knowledge().known(&app.data.shotgun);
+    let frame_area = frame.area();
+    if known.is_empty() || frame_area.height < 4 {
+        return;
+    }
+    let area = Rect {
+        x: frame_area.x + 1,
+        y: frame_area.bottom() - 3,
+        width: frame_area.width.saturating_sub(2),
+        height: 1,
+    };
+
+    let mut spans = vec![Span::raw("Your known shells: ")];
+    for (depth, shell) in known {
+        let place = match depth {
+            0 => "next".to_string(),
+            _ => format!("#{}", depth + 1),
+        };
+        spans.push(Span::styled(format!("{} {:?}  ", place, shell), Style::default().fg(Color::Yellow)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+//the lesson text sits above the shotgun so the table stays visible
+pub fn render_tutorial_popup(app: &App, frame: &mut Frame) {
+    let Some(tutorial) = &app.tutorial else {
+        return;
+    };
+    let Some(step) = tutorial.current() else {
+        return;
+    };
+
+    let frame_area = frame.area();
+    let width = (frame_area.width as f32 * 0.6) as u16;
+    let height = (8 + tutorial.notes.len() as u16 * 2).min(frame_area.height / 2);
+    let area = Rect {
+        x: frame_area.x + (frame_area.width.saturating_sub(width)) / 2,
+        y: frame_area.y + 1,
         width,
         height,
     };
 
-    let log_content = app.logger.get_window().iter().map(|s| s.as_str()).collect::<Vec<_>>().join("\n");
-    let mut log_popup = Paragraph::new(log_content)
-        .block(Block::default().title("Message Log - use j k to navigate").borders(Borders::ALL))
-        .wrap(Wrap {trim: true})
-        .scroll((( app.logger.log_scroll as u16), 0));
-    if app.widget_data.is_focused(WidgetKind::Log) {
-        log_popup = log_popup.set_style(Style::default().fg(Color::LightRed));
+    let mut lines = vec![Line::from(step.text), Line::from("")];
+    for note in &tutorial.notes {
+        lines.push(Line::from(Span::styled(*note, Style::default().fg(Color::Cyan))));
     }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(tutorial.hint(), Style::default().fg(Color::Yellow))));
+
+    let (current, total) = tutorial.progress();
+    let popup = Paragraph::new(lines)
+        .block(Block::default()
+            .title(format!("Tutorial {}/{}", current, total))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded))
+        .wrap(Wrap { trim: true });
 
     frame.render_widget(Clear, area);
-    frame.render_widget(log_popup, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Overlay);
+    frame.render_widget(popup, area);
 }
 
+//hand editor for the sandbox, docked on the right
+pub fn render_sandbox_panel(app: &App, frame: &mut Frame) {
+    let Some(sandbox) = &app.sandbox else {
+        return;
+    };
+
+    let frame_area = frame.area();
+    let width = 44.min(frame_area.width.saturating_sub(2));
+    let height = 16.min(frame_area.height.saturating_sub(4));
+    let area = Rect {
+        x: frame_area.right().saturating_sub(width + 1),
+        y: frame_area.y + 1,
+        width,
+        height,
+    };
 
-fn render_inventory_popup(app: &App, frame: &mut Frame, chunks: &[Rect]) {
-    let area = chunks[2];
-    let log_content = app.logger.log.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("\n");
-    let mut log_popup = Paragraph::new(log_content)
-        .block(Block::default().title("Message Log").borders(Borders::ALL))
-        .wrap(Wrap {trim: true})
-        .scroll(((app.logger.log_scroll as u16), 0));
-    if app.widget_data.is_focused(WidgetKind::Inventory) {
-        log_popup = log_popup.set_style(Style::default().fg(Color::LightRed))
+    let player = &app.data.players[sandbox.player];
+    let turn = app.data.match_data.turn.map_or("nobody", |turn| app.data.players[turn].name());
+    let mut lines = Vec::new();
+    for (idx, row) in SandboxRow::ALL.iter().enumerate() {
+        let value = match row {
+            SandboxRow::Shell => format!("< {:?} >  enter: queue", sandbox.selected_shell()),
+            SandboxRow::Load => format!("{:?}  enter: load", sandbox.queued),
+            SandboxRow::State => format!("< {:?} >", app.data.shotgun.state),
+            SandboxRow::Model => format!("< {:?} >", app.data.shotgun.model),
+            SandboxRow::Player => format!("< {} >", player.name()),
+            SandboxRow::Health => format!("< {} >", player.health()),
+            SandboxRow::Item => format!("< {:?} >  enter: give", sandbox.selected_item()),
+            SandboxRow::Turn => format!("< {} >", turn),
+        };
+        let line = format!("{:<7} {}", format!("{:?}", row), value);
+        if idx == sandbox.row {
+            lines.push(Line::from(Span::styled(format!("> {}", line), Style::default().fg(Color::LightRed))));
+        } else {
+            lines.push(Line::from(format!("  {}", line)));
+        }
     }
 
+    //the sandbox is for studying, so the chamber is not a secret here
+    let mut chamber: Vec<String> = app.data.shotgun.shells.borrow().iter().map(|shell| format!("{:?}", shell)).collect();
+    chamber.reverse();
+    lines.push(Line::from(""));
+    lines.push(Line::from(format!("Chamber: {}", chamber.join(", "))));
+    lines.push(Line::from(format!("{} items: {:?}", player.name(), player.items())));
+
+    let panel = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Sandbox - arrows, enter, backspace")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded))
+        .wrap(Wrap { trim: true });
+
     frame.render_widget(Clear, area);
-    frame.render_widget(log_popup, chunks[2]);
+    app.hit_map.borrow_mut().add(area, HitTarget::Overlay);
+    frame.render_widget(panel, area);
 }
 
-fn render_player_popup(app: &App, frame: &mut Frame) {
+//the items of the last reload, laid on the table one by one
+pub fn render_loot_popup(app: &App, frame: &mut Frame) {
+    let Some(loot) = &app.loot else {
+        return;
+    };
 
+    let frame_area = frame.area();
+    let width = 60.min(frame_area.width.saturating_sub(2));
+    let height = (loot.deals.len() as u16 * 2 + 2).min(frame_area.height.saturating_sub(4));
     let area = Rect {
-        x: 10,
-        y: 5,
-        width: 10,
-        height: 10,
+        x: frame_area.x + (frame_area.width.saturating_sub(width)) / 2,
+        y: frame_area.bottom().saturating_sub(height + 3),
+        width,
+        height,
     };
 
-    // The "icon" — can be emoji, unicode, ASCII art, etc.
-
-    let mut player_popup = Paragraph::new(PLAYER_ART)
-        .block(Block::default().title("Popup").borders(Borders::NONE));
-    if app.widget_data.is_focused(WidgetKind::Data) {
-        player_popup = player_popup.set_style(Style::default().fg(Color::LightRed))
+    let mut left = loot.shown();
+    let mut lines = Vec::new();
+    for deal in &loot.deals {
+        let mut spans = vec![Span::raw(format!("{:<7} ", app.data.players[deal.player].name()))];
+        for item in deal.items.iter().take(left) {
+            spans.push(Span::styled(format!("[{:?}] ", item), Style::default().fg(rarity_color(item.rarity()))));
+        }
+        left = left.saturating_sub(deal.items.len());
+        lines.push(Line::from(spans));
+        lines.push(Line::from(""));
     }
 
+    let popup = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Items")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded))
+        .wrap(Wrap { trim: true });
+
     frame.render_widget(Clear, area);
-    frame.render_widget(player_popup, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Overlay);
+    frame.render_widget(popup, area);
 }
 
-//begin changing "popups" to not be such as shotgun and inventory
-fn render_shotgun_popup(app: &App, frame: &mut Frame) {
+
+//drops down from the top and covers the table, like a quake console
+fn render_console(app: &App, frame: &mut Frame) {
     let frame_area = frame.area();
-    let w = 68;
-    let h = 10;
-    let w = w.min(frame_area.width);
-    let h = h.min(frame_area.height);
+    let area = Rect {
+        height: (frame_area.height as f32 * 0.4) as u16,
+        ..frame_area
+    };
+    let output_lines = area.height.saturating_sub(3) as usize;
+
+    let mut lines: Vec<Line> = app.console
+        .tail(output_lines)
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Yellow)),
+        Span::raw(app.console.input.as_str()),
+        Span::styled("_", Style::default().fg(Color::Yellow)),
+    ]));
+
+    let console = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Console - ` to close, `help` for commands")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)))
+        .style(Style::default().bg(Color::Black));
 
-    let x = frame_area.x + (frame_area.width - w) / 2;
-    let y = frame_area.y + (frame_area.height - h) / 2;
+    frame.render_widget(Clear, area);
+    frame.render_widget(console, area);
+}
 
-    let area = Rect { x, y, width: w, height: h };
-    let mut shotgun_popup = Paragraph::new(SHOTGUN_ART)
-        .block(Block::default().borders(Borders::empty()));
+fn render_help(app: &App, frame: &mut Frame) {
+    let area = centered_rect(70, 80, frame.area());
+    let page = app.help.page();
+    let lines = ui_help::page_lines(page, &app.keymap, &screens::contexts(app));
+    let title = format!(
+        "Help - {} ({}/{}) - left/right for pages, ? to close",
+        page.title(), app.help.page + 1, HelpPage::ALL.len()
+    );
 
-    if app.widget_data.is_focused(WidgetKind::Shotgun) {
-        shotgun_popup = shotgun_popup.set_style(Style::default().fg(Color::LightRed))
-    }
+    let help = Paragraph::new(lines)
+        .block(Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded))
+        .wrap(Wrap { trim: false })
+        .scroll((app.help.scroll, 0));
 
     frame.render_widget(Clear, area);
-    frame.render_widget(shotgun_popup, area);
+    frame.render_widget(help, area);
+    app.hit_map.borrow_mut().add(area, HitTarget::Help);
 }
 
+//sits on the bottom of the screen, over the status bar
+fn render_palette(app: &App, frame: &mut Frame) {
+    let frame_area = frame.area();
+    let height = 4.min(frame_area.height);
+    let area = Rect {
+        y: frame_area.bottom() - height,
+        height,
+        ..frame_area
+    };
+
+    let hint = match (&app.palette.error, app.palette.candidates.is_empty()) {
+        (Some(err), _) => Line::from(Span::styled(err.as_str(), Style::default().fg(Color::Red))),
+        (None, false) => Line::from(Span::styled(app.palette.candidates.join("  "), Style::default().fg(Color::DarkGray))),
+        (None, true) => Line::from(""),
+    };
+    let lines = vec![
+        hint,
+        Line::from(vec![
+            Span::styled(":", Style::default().fg(Color::Yellow)),
+            Span::raw(app.palette.input.as_str()),
+            Span::styled("_", Style::default().fg(Color::Yellow)),
+        ]),
+    ];
+
+    let palette = Paragraph::new(lines)
+        .block(Block::default()
+            .title("Command - tab to complete, enter to run, esc to close")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)))
+        .style(Style::default().bg(Color::Black));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(palette, area);
+}
+
+fn render_dialog(dialog: &Dialog, frame: &mut Frame) {
+    let hint = |text: &'static str| Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)));
+    let selected = Style::default().fg(Color::Black).bg(Color::LightRed);
+
+    let mut lines = vec![Line::from(dialog.body.clone()), Line::from("")];
+    match &dialog.kind {
+        DialogKind::Message => lines.push(hint("press any key")),
+        DialogKind::Confirm { on_yes, .. } => {
+            let button = |label: &str, on: bool| {
+                Span::styled(format!("[ {} ]", label), if on { selected } else { Style::default() })
+            };
+            lines.push(Line::from(vec![button("Yes", *on_yes), Span::raw("  "), button("No", !*on_yes)]));
+            lines.push(Line::from(""));
+            lines.push(hint("y or n, or arrows and enter"));
+        },
+        DialogKind::Choice { options, cursor } => {
+            for (idx, (label, _)) in options.iter().enumerate() {
+                let text = format!("{} {}", idx + 1, label);
+                lines.push(Line::from(Span::styled(text, if idx == *cursor { selected } else { Style::default() })));
+            }
+            lines.push(Line::from(""));
+            lines.push(hint("arrows and enter or a number, esc to cancel"));
+        },
+        DialogKind::Input { text, .. } => {
+            lines.push(Line::from(format!("> {}_", text)));
+            lines.push(Line::from(""));
+            lines.push(hint("enter to confirm, esc to cancel"));
+        },
+    }
 
-//want to make a popup to confirm things
-fn render_confirm_popup(app: &App, frame: &mut Frame) {
-    //three rects/ one big one and two small ones
     let frame_area = frame.area();
-    let w = frame_area.width;
-    let h = frame_area.height;
+    let width = 56.min(frame_area.width.saturating_sub(4));
+    //the body wraps, everything else is one line each
+    let body_rows = (dialog.body.chars().count() as u16).div_ceil(width.saturating_sub(2).max(1)).max(1);
+    let height = (lines.len() as u16 + body_rows + 1).min(frame_area.height);
+    let area = Rect {
+        x: frame_area.x + (frame_area.width.saturating_sub(width)) / 2,
+        y: frame_area.y + (frame_area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    let popup = Paragraph::new(lines)
+        .block(Block::default()
+            .title(dialog.title.clone())
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Red)))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
 }
+
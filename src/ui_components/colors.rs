@@ -1,11 +1,28 @@
 //this is essentially a config file and is what all of the widgets are based off of
 use hex_color::HexColor;
+use ratatui::style::Color;
 
-struct Colors {
+pub struct Colors {
 
-    background: HexColor,
-    primary: HexColor,
-    secondary: HexColor,
-    active: HexColor,
+    pub background: HexColor,
+    pub primary: HexColor,
+    pub secondary: HexColor,
+    pub active: HexColor,
 
 }
+
+impl Default for Colors {
+    fn default() -> Self {
+        Colors {
+            background: HexColor::rgb(16, 24, 48),
+            primary: HexColor::rgb(230, 230, 230),
+            secondary: HexColor::rgb(200, 60, 60),
+            active: HexColor::rgb(255, 140, 140),
+        }
+    }
+}
+
+/// converts a theme color into something ratatui can draw
+pub fn to_color(color: HexColor) -> Color {
+    Color::Rgb(color.r, color.g, color.b)
+}
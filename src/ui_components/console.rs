@@ -0,0 +1,82 @@
+//console.rs
+//developer console toggled with the backtick, it keeps its own scrollback and input history
+use std::collections::VecDeque;
+
+use crate::ui_components::history::History;
+
+const OUTPUT_SIZE: usize = 200;
+
+#[derive(Debug)]
+pub struct Console {
+    pub open: bool,
+    pub input: String,
+    output: VecDeque<String>,
+    history: History,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Console {
+            open: false,
+            input: String::new(),
+            output: VecDeque::new(),
+            history: History::new(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// takes the typed line, echoes it and remembers it for the history
+    pub fn submit(&mut self) -> Option<String> {
+        let line = std::mem::take(&mut self.input);
+        self.history.reset();
+        if line.trim().is_empty() {
+            return None;
+        }
+        self.print(format!("> {}", line));
+        self.history.push(line.clone());
+        Some(line)
+    }
+
+    pub fn print(&mut self, line: String) {
+        if self.output.len() >= OUTPUT_SIZE {
+            self.output.pop_front();
+        }
+        self.output.push_back(line);
+    }
+
+    pub fn clear(&mut self) {
+        self.output.clear();
+    }
+
+    pub fn history_prev(&mut self) {
+        if let Some(line) = self.history.back() {
+            self.input = line.to_string();
+        }
+    }
+
+    pub fn history_next(&mut self) {
+        self.input = self.history.forward().map(str::to_string).unwrap_or_default();
+    }
+
+    /// the last `lines` lines of output
+    pub fn tail(&self, lines: usize) -> impl Iterator<Item = &String> {
+        self.output.iter().skip(self.output.len().saturating_sub(lines))
+    }
+}
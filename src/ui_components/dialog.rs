@@ -0,0 +1,158 @@
+//dialog.rs
+//modal dialogs, the topmost one takes every key until it is answered and hands its answer back as an event
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use crate::event::AppEvent;
+
+#[derive(Debug, Clone)]
+pub enum DialogKind {
+    /// just something to read, any key closes it
+    Message,
+    /// yes or no, `no` is also what esc answers
+    Confirm {
+        yes: AppEvent,
+        no: Option<AppEvent>,
+        /// whether enter answers yes
+        on_yes: bool,
+    },
+    /// one of several, esc picks none
+    Choice {
+        options: Vec<(String, AppEvent)>,
+        cursor: usize,
+    },
+    /// a line of text, handed to `submit` on enter
+    Input {
+        text: String,
+        submit: fn(String) -> AppEvent,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Dialog {
+    pub title: String,
+    pub body: String,
+    pub kind: DialogKind,
+}
+
+/// what a key did to the dialog
+#[derive(Debug, Clone)]
+pub enum DialogOutcome {
+    Open,
+    /// answered or dismissed, with the event to send if there is one
+    Closed(Option<AppEvent>),
+}
+
+impl Dialog {
+    pub fn message(title: &str, body: String) -> Self {
+        Dialog { title: title.to_string(), body, kind: DialogKind::Message }
+    }
+
+    pub fn confirm(title: &str, body: String, yes: AppEvent, no: Option<AppEvent>) -> Self {
+        Dialog { title: title.to_string(), body, kind: DialogKind::Confirm { yes, no, on_yes: false } }
+    }
+
+    pub fn choice(title: &str, body: String, options: Vec<(String, AppEvent)>) -> Self {
+        Dialog { title: title.to_string(), body, kind: DialogKind::Choice { options, cursor: 0 } }
+    }
+
+    /// starts out with `text` typed in
+    pub fn input(title: &str, body: String, text: String, submit: fn(String) -> AppEvent) -> Self {
+        Dialog { title: title.to_string(), body, kind: DialogKind::Input { text, submit } }
+    }
+
+    pub fn handle(&mut self, key: KeyEvent) -> DialogOutcome {
+        match &mut self.kind {
+            DialogKind::Message => DialogOutcome::Closed(None),
+            DialogKind::Confirm { yes, no, on_yes } => match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => DialogOutcome::Closed(Some(yes.clone())),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => DialogOutcome::Closed(no.clone()),
+                KeyCode::Enter if *on_yes => DialogOutcome::Closed(Some(yes.clone())),
+                KeyCode::Enter => DialogOutcome::Closed(no.clone()),
+                KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::Char('h') | KeyCode::Char('l') => {
+                    *on_yes = !*on_yes;
+                    DialogOutcome::Open
+                },
+                _ => DialogOutcome::Open,
+            },
+            DialogKind::Choice { options, cursor } => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    *cursor = if *cursor == 0 { options.len().saturating_sub(1) } else { *cursor - 1 };
+                    DialogOutcome::Open
+                },
+                KeyCode::Down | KeyCode::Char('j') => {
+                    *cursor = if *cursor + 1 >= options.len() { 0 } else { *cursor + 1 };
+                    DialogOutcome::Open
+                },
+                KeyCode::Enter | KeyCode::Char(' ') => {
+                    DialogOutcome::Closed(options.get(*cursor).map(|(_, event)| event.clone()))
+                },
+                KeyCode::Char(digit @ '1'..='9') => {
+                    let idx = digit as usize - '1' as usize;
+                    match options.get(idx) {
+                        Some((_, event)) => DialogOutcome::Closed(Some(event.clone())),
+                        None => DialogOutcome::Open,
+                    }
+                },
+                KeyCode::Esc => DialogOutcome::Closed(None),
+                _ => DialogOutcome::Open,
+            },
+            DialogKind::Input { text, submit } => match key.code {
+                KeyCode::Enter => DialogOutcome::Closed(Some(submit(text.trim().to_string()))),
+                KeyCode::Esc => DialogOutcome::Closed(None),
+                KeyCode::Backspace => {
+                    text.pop();
+                    DialogOutcome::Open
+                },
+                KeyCode::Char(c) => {
+                    text.push(c);
+                    DialogOutcome::Open
+                },
+                _ => DialogOutcome::Open,
+            },
+        }
+    }
+}
+
+/// dialogs waiting for an answer, the last one is on top
+#[derive(Debug, Default)]
+pub struct Dialogs {
+    stack: Vec<Dialog>,
+}
+
+impl Dialogs {
+    pub fn new() -> Self {
+        Dialogs { stack: Vec::new() }
+    }
+
+    pub fn push(&mut self, dialog: Dialog) {
+        self.stack.push(dialog);
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    /// bottom first, the order they are drawn in
+    pub fn iter(&self) -> impl Iterator<Item = &Dialog> {
+        self.stack.iter()
+    }
+
+    /// gives the key to the topmost dialog, returns its answer once it closes
+    pub fn handle(&mut self, key: KeyEvent) -> Option<AppEvent> {
+        let outcome = self.stack.last_mut()?.handle(key);
+        match outcome {
+            DialogOutcome::Open => None,
+            DialogOutcome::Closed(event) => {
+                self.stack.pop();
+                event
+            },
+        }
+    }
+
+    /// closes the topmost dialog without an answer, only a message can be clicked away
+    pub fn dismiss_message(&mut self) {
+        if matches!(self.stack.last(), Some(Dialog { kind: DialogKind::Message, .. })) {
+            self.stack.pop();
+        }
+    }
+}
@@ -1,17 +1,63 @@
 //grid will be used for the inventory
 
-use ratatui::{
-    prelude::*,
-    widgets::{Block, Borders, Paragraph},
-};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, BorderType, Paragraph};
 
+/// one slot of the grid, an empty glyph and label draw an empty slot
+#[derive(Debug, Clone, Default)]
+pub struct GridCell {
+    pub glyph: String,
+    pub label: String,
+    pub style: Style,
+}
+
+/// `slots` slots laid out `cols` x `rows`, filled row by row from `cells`
 pub struct Grid {
     pub cols: usize,
     pub rows: usize,
+    /// the last row can be short
+    pub slots: usize,
+    pub cells: Vec<GridCell>,
+    /// slot drawn with a highlighted border
+    pub selected: Option<usize>,
+}
+
+/// where each of the `cols` x `rows` slots goes in `area`, row by row
+pub fn slot_areas(cols: usize, rows: usize, area: Rect) -> Vec<Rect> {
+    if cols == 0 || rows == 0 {
+        return Vec::new();
+    }
+    Layout::vertical(vec![Constraint::Ratio(1, rows as u32); rows])
+        .split(area)
+        .iter()
+        .flat_map(|row_area| Layout::horizontal(vec![Constraint::Ratio(1, cols as u32); cols]).split(*row_area).to_vec())
+        .collect()
 }
 
 impl Widget for Grid {
 
     fn render(self, area: Rect, buf: &mut Buffer) {
+        for (idx, slot_area) in slot_areas(self.cols, self.rows, area).into_iter().take(self.slots).enumerate() {
+            let cell = self.cells.get(idx).cloned().unwrap_or_default();
+
+            let border_style = if self.selected == Some(idx) {
+                Style::default().fg(Color::LightRed)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            //slots are numbered like the keys that use them
+            let block = Block::bordered()
+                .title(format!("{}", idx + 1))
+                .border_type(BorderType::Rounded)
+                .border_style(border_style);
+
+            let slot = Paragraph::new(vec![
+                Line::from(Span::styled(cell.glyph, cell.style)),
+                Line::from(Span::styled(cell.label, cell.style)),
+            ])
+                .alignment(Alignment::Center)
+                .block(block);
+            slot.render(slot_area, buf);
+        }
     }
 }
@@ -0,0 +1,51 @@
+//history.rs
+//lines typed into the console or the palette, walked with up and down like a shell
+#[derive(Debug, Default)]
+pub struct History {
+    lines: Vec<String>,
+    /// position while walking back, `None` when editing a fresh line
+    idx: Option<usize>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History { lines: Vec::new(), idx: None }
+    }
+
+    pub fn push(&mut self, line: String) {
+        self.lines.push(line);
+        self.idx = None;
+    }
+
+    /// stops walking, the next `back` starts from the newest line again
+    pub fn reset(&mut self) {
+        self.idx = None;
+    }
+
+    /// one line further back, `None` if there is nothing to go back to
+    pub fn back(&mut self) -> Option<&str> {
+        if self.lines.is_empty() {
+            return None;
+        }
+        let idx = match self.idx {
+            Some(idx) => idx.saturating_sub(1),
+            None => self.lines.len() - 1,
+        };
+        self.idx = Some(idx);
+        Some(&self.lines[idx])
+    }
+
+    /// one line forward, `None` once past the newest line
+    pub fn forward(&mut self) -> Option<&str> {
+        match self.idx {
+            Some(idx) if idx + 1 < self.lines.len() => {
+                self.idx = Some(idx + 1);
+                Some(&self.lines[idx + 1])
+            },
+            _ => {
+                self.idx = None;
+                None
+            },
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//hit_map.rs
+//where everything was drawn on the last frame, so a mouse event can find what is under it
+use ratatui::layout::{Position, Rect};
+
+use crate::ui_components::widget_data::WidgetKind;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HitTarget {
+    /// a window, clicking focuses it
+    Widget(WidgetKind),
+    /// clicking fires at the player being aimed at
+    Shotgun,
+    /// clicking aims the shotgun at them
+    Player(usize),
+    /// clicking uses the item in it
+    InventorySlot(usize),
+    /// the help overlay
+    Help,
+    /// a panel or popup drawn over the table, it has nothing to click but keeps clicks off what is under it
+    Overlay,
+}
+
+#[derive(Debug, Default)]
+pub struct HitMap {
+    /// in drawing order, later areas are on top
+    areas: Vec<(Rect, HitTarget)>,
+    /// the whole terminal on the last frame
+    pub bounds: Rect,
+}
+
+impl HitMap {
+    pub fn new() -> Self {
+        HitMap { areas: Vec::new(), bounds: Rect::default() }
+    }
+
+    /// forgets the last frame, called before drawing the next one into `bounds`
+    pub fn clear(&mut self, bounds: Rect) {
+        self.areas.clear();
+        self.bounds = bounds;
+    }
+
+    pub fn add(&mut self, area: Rect, target: HitTarget) {
+        self.areas.push((area, target));
+    }
+
+    /// everything under the cell at `column`, `row`, topmost first
+    pub fn at(&self, column: u16, row: u16) -> Vec<HitTarget> {
+        self.areas
+            .iter()
+            .rev()
+            .filter(|(area, _)| area.contains(Position { x: column, y: row }))
+            .map(|(_, target)| *target)
+            .collect()
+    }
+
+    /// where `target` was drawn last frame
+    pub fn area_of(&self, target: HitTarget) -> Option<Rect> {
+        self.areas
+            .iter()
+            .rev()
+            .find(|(_, drawn)| *drawn == target)
+            .map(|(area, _)| *area)
+    }
+}
@@ -0,0 +1,194 @@
+//inspector.rs
+//tree view of the game state for the Data popup, rebuilt from `App` every time it is drawn
+use std::collections::HashSet;
+
+use crate::app::App;
+use crate::data::PLAYER;
+
+/// keys the inspector reacts to while the Data popup is focused
+#[derive(Debug, Clone, Copy)]
+pub enum InspectorInput {
+    Up,
+    Down,
+    Expand,
+    Collapse,
+    Toggle,
+}
+
+#[derive(Debug, Clone)]
+pub struct InspectorNode {
+    pub label: String,
+    pub children: Vec<InspectorNode>,
+}
+
+impl InspectorNode {
+    fn leaf(label: String) -> Self {
+        InspectorNode { label, children: Vec::new() }
+    }
+
+    fn branch(label: &str, children: Vec<InspectorNode>) -> Self {
+        InspectorNode { label: label.to_string(), children }
+    }
+}
+
+/// one line of the flattened tree as it is shown
+#[derive(Debug, Clone)]
+pub struct InspectorRow {
+    pub depth: usize,
+    /// labels from the root down, used to remember what is expanded
+    pub path: String,
+    pub label: String,
+    pub has_children: bool,
+    pub expanded: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Inspector {
+    expanded: HashSet<String>,
+    pub cursor: usize,
+}
+
+impl Inspector {
+    pub fn new() -> Self {
+        Inspector::default()
+    }
+
+    /// the rows currently visible, children of collapsed nodes are skipped
+    pub fn rows(&self, tree: &[InspectorNode]) -> Vec<InspectorRow> {
+        let mut rows = Vec::new();
+        self.flatten(tree, 0, "", &mut rows);
+        rows
+    }
+
+    fn flatten(&self, nodes: &[InspectorNode], depth: usize, parent: &str, rows: &mut Vec<InspectorRow>) {
+        for node in nodes {
+            //labels can change ("items (2)"), so the path only keeps the part before the count
+            let key = node.label.split(" (").next().unwrap_or(&node.label);
+            let path = format!("{}/{}", parent, key);
+            let expanded = self.expanded.contains(&path);
+            rows.push(InspectorRow {
+                depth,
+                path: path.clone(),
+                label: node.label.clone(),
+                has_children: !node.children.is_empty(),
+                expanded,
+            });
+            if expanded {
+                self.flatten(&node.children, depth + 1, &path, rows);
+            }
+        }
+    }
+
+    pub fn handle(&mut self, input: InspectorInput, tree: &[InspectorNode]) {
+        let rows = self.rows(tree);
+        if rows.is_empty() {
+            return;
+        }
+        self.cursor = self.cursor.min(rows.len() - 1);
+        let row = &rows[self.cursor];
+
+        match input {
+            InspectorInput::Up => self.cursor = self.cursor.saturating_sub(1),
+            InspectorInput::Down => self.cursor = (self.cursor + 1).min(rows.len() - 1),
+            InspectorInput::Expand if row.has_children => {
+                self.expanded.insert(row.path.clone());
+            },
+            InspectorInput::Collapse if row.expanded => {
+                self.expanded.remove(&row.path);
+            },
+            //collapsing a leaf or a closed node jumps to its parent
+            InspectorInput::Collapse => {
+                if let Some(parent) = rows[..self.cursor].iter().rposition(|other| other.depth < row.depth) {
+                    self.cursor = parent;
+                }
+            },
+            InspectorInput::Toggle if row.expanded => {
+                self.expanded.remove(&row.path);
+            },
+            InspectorInput::Toggle if row.has_children => {
+                self.expanded.insert(row.path.clone());
+            },
+            _ => {},
+        }
+    }
+}
+
+/// the state worth looking at, shell order and the seed stay hidden unless debug mode is on
+pub fn build_tree(app: &App) -> Vec<InspectorNode> {
+    let data = &app.data;
+    let shells = data.shotgun.shells.borrow();
+
+    let shell_children = if app.debug {
+        shells.iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, shell)| InspectorNode::leaf(format!("{}. {:?}", idx + 1, shell)))
+            .collect()
+    } else {
+        vec![InspectorNode::leaf("hidden, turn on debug mode to see".to_string())]
+    };
+    let shotgun = InspectorNode::branch("Shotgun", vec![
+        InspectorNode::leaf(format!("model: {:?}", data.shotgun.model)),
+        InspectorNode::leaf(format!("state: {:?}", data.shotgun.state)),
+        InspectorNode::branch(&format!("shells ({})", shells.len()), shell_children),
+    ]);
+
+    let turn = data.match_data.turn.map_or("nobody", |turn| data.players[turn].name());
+    let mut match_children = vec![
+        InspectorNode::leaf(format!("round: {}", data.match_data.round_count)),
+        InspectorNode::leaf(format!("turn: {}", turn)),
+    ];
+    if app.debug {
+        match_children.push(InspectorNode::leaf(format!("seed: {}", data.seed)));
+    }
+    let match_data = InspectorNode::branch("Match", match_children);
+
+    let players = InspectorNode::branch("Players", data.players
+        .iter()
+        .enumerate()
+        .map(|(idx, player)| {
+            let items = player.items()
+                .iter()
+                .map(|item| InspectorNode::leaf(format!("{:?}", item)))
+                .collect();
+            //what someone else has seen is as secret as the shells themselves
+            let knows = if app.debug || idx == PLAYER {
+                player.knowledge()
+                    .known(&data.shotgun)
+                    .into_iter()
+                    .map(|(depth, shell)| InspectorNode::leaf(format!("{}. {:?}", depth + 1, shell)))
+                    .collect()
+            } else {
+                vec![InspectorNode::leaf("hidden, turn on debug mode to see".to_string())]
+            };
+            InspectorNode::branch(player.name(), vec![
+                InspectorNode::leaf(format!("health: {}", player.health())),
+                InspectorNode::branch(&format!("status ({})", player.statuses().len()), player.statuses()
+                    .iter()
+                    .map(|status| InspectorNode::leaf(status.label()))
+                    .collect()),
+                InspectorNode::branch(&format!("items ({})", player.items().len()), items),
+                InspectorNode::branch("knows", knows),
+            ])
+        })
+        .collect());
+
+    let mut run_children = Vec::new();
+    if let Some(run) = &app.endless {
+        run_children.push(InspectorNode::leaf(format!("stage: {}", run.stage)));
+        run_children.push(InspectorNode::leaf(format!("score: {}", run.score)));
+        run_children.push(InspectorNode::leaf(format!("multiplier: x{}", run.multiplier)));
+    }
+    if let Some(daily) = &app.daily {
+        run_children.push(InspectorNode::leaf(format!("daily: {}", daily.challenge.date)));
+    }
+    let run = InspectorNode::branch("Run", run_children);
+
+    let window = app.logger.get_window();
+    let logger = InspectorNode::branch(&format!("Log window ({})", window.len()), window
+        .iter()
+        .map(|entry| InspectorNode::leaf(entry.text.clone()))
+        .collect());
+
+    vec![shotgun, match_data, players, run, logger]
+}
@@ -0,0 +1,43 @@
+//inventory.rs
+//cursor over the item slots of the inventory panel, as many as the rules allow
+
+/// columns of the slot grid, the rows follow from the inventory size
+pub const SLOT_COLS: usize = 4;
+
+/// rows of the slot grid for an inventory of `size` items
+pub fn slot_rows(size: usize) -> usize {
+    size.div_ceil(SLOT_COLS).max(1)
+}
+
+/// keys that move the cursor while the inventory is focused
+#[derive(Debug, Clone, Copy)]
+pub enum InventoryInput {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Default)]
+pub struct Inventory {
+    /// selected slot, counted row by row from the top left
+    pub cursor: usize,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Inventory::default()
+    }
+
+    /// moves the cursor over an inventory of `size` slots
+    pub fn handle(&mut self, input: InventoryInput, size: usize) {
+        let (row, col) = (self.cursor / SLOT_COLS, self.cursor % SLOT_COLS);
+        let (row, col) = match input {
+            InventoryInput::Up => (row.saturating_sub(1), col),
+            InventoryInput::Down => ((row + 1).min(slot_rows(size) - 1), col),
+            InventoryInput::Left => (row, col.saturating_sub(1)),
+            InventoryInput::Right => (row, (col + 1).min(SLOT_COLS - 1)),
+        };
+        self.cursor = (row * SLOT_COLS + col).min(size.saturating_sub(1));
+    }
+}
@@ -1,92 +1,582 @@
+//logger.rs
+//every message of the session, tagged so the log pane can color and filter them
 use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// entries kept before the oldest are dropped
+const HISTORY_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogCategory {
+    /// what happens at the table
+    Game,
+    /// presets, files and other things outside the match
+    System,
+    /// someone talking to the player, like the tutorial
+    Chat,
+    /// for working on the game, hidden unless asked for
+    Debug,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 4] = [
+        LogCategory::Game,
+        LogCategory::System,
+        LogCategory::Chat,
+        LogCategory::Debug,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogCategory::Game => "game",
+            LogCategory::System => "system",
+            LogCategory::Chat => "chat",
+            LogCategory::Debug => "debug",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// since the game was started
+    pub time: Duration,
+    pub round: usize,
+    /// the name of whoever's turn it was, `None` outside a match
+    pub turn: Option<String>,
+    pub category: LogCategory,
+    pub severity: Severity,
+    pub text: String,
+}
+
+impl LogEntry {
+    fn new(category: LogCategory, text: impl Into<String>) -> Self {
+        LogEntry {
+            time: Duration::ZERO,
+            round: 0,
+            turn: None,
+            category,
+            severity: Severity::Info,
+            text: text.into(),
+        }
+    }
+
+    pub fn game(text: impl Into<String>) -> Self {
+        Self::new(LogCategory::Game, text)
+    }
+
+    pub fn system(text: impl Into<String>) -> Self {
+        Self::new(LogCategory::System, text)
+    }
+
+    pub fn chat(text: impl Into<String>) -> Self {
+        Self::new(LogCategory::Chat, text)
+    }
+
+    pub fn debug(text: impl Into<String>) -> Self {
+        Self::new(LogCategory::Debug, text)
+    }
+
+    pub fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    pub fn error(mut self) -> Self {
+        self.severity = Severity::Error;
+        self
+    }
+
+    /// happened during `name`'s turn, for entries logged after the turn moved on
+    pub fn during(mut self, name: &str) -> Self {
+        self.turn = Some(name.to_string());
+        self
+    }
+}
+
+/// one line per entry in a saved log, like `[01:23] round 2 (You) game: You shoot the Dealer`
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.time.as_secs();
+        write!(f, "[{:02}:{:02}] round {}", secs / 60, secs % 60, self.round)?;
+        if let Some(turn) = &self.turn {
+            write!(f, " ({})", turn)?;
+        }
+        write!(f, " {}", self.category.name())?;
+        match self.severity {
+            Severity::Info => {},
+            Severity::Warning => write!(f, " warning")?,
+            Severity::Error => write!(f, " error")?,
+        }
+        write!(f, ": {}", self.text)
+    }
+}
+
+/// which entries the log pane shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFilter {
+    All,
+    Only(LogCategory),
+}
+
+impl LogFilter {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogFilter::All => "all",
+            LogFilter::Only(category) => category.name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogInput {
+    /// the next category, then everything again
+    Filter,
+    /// show or hide debug entries
+    Debug,
+    PageUp,
+    PageDown,
+    /// the oldest entry kept
+    Oldest,
+    /// back to the newest entry, following new ones as they come
+    Newest,
+    /// start typing a search
+    Search,
+    /// the next match further back
+    OlderMatch,
+    /// the next match towards the newest
+    NewerMatch,
+}
 
 #[derive(Debug)]
 pub struct Logger {
-    pub log: VecDeque<String>,
-    history_size: usize,
+    pub log: VecDeque<LogEntry>,
+    started: Instant,
+    pub filter: LogFilter,
+    /// debug entries are left out unless this is on
+    pub show_debug: bool,
+
+    /// highlighted in the log, empty for no search
+    pub search: String,
+    /// whether keys are being typed into the search
+    pub searching: bool,
+    /// the match the view was last moved to, as an index into the filtered entries
+    current_match: Option<usize>,
 
     //display specific
     window_size: usize,
-    window: Vec<String>,
+    window: Vec<LogEntry>,
+    window_start: usize,
+    /// lines back from the newest, 0 follows new entries as they come
     pub log_scroll: usize,
+    /// entries that came in while scrolled back
+    unseen: usize,
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Logger {
     pub fn new() -> Self {
         Logger {
             log: VecDeque::new(),
-            history_size: 250,
+            started: Instant::now(),
+            filter: LogFilter::All,
+            show_debug: false,
+            search: String::new(),
+            searching: false,
+            current_match: None,
             window_size: 0,
             window: Vec::new(),
+            window_start: 0,
             log_scroll: 0,
+            unseen: 0,
         }
     }
 
-    pub fn send_log(&mut self, message: Option<String>) {
-        if let Some(msg) = message {
-            if self.log.len() >= self.history_size {
-                self.log.pop_front();
+    /// stamps `entry` with the time and keeps it, the caller fills in the round and turn
+    pub fn push(&mut self, mut entry: LogEntry) {
+        entry.time = self.started.elapsed();
+        if self.log.len() >= HISTORY_SIZE {
+            let dropped = self.log.pop_front();
+            //the match moves up with everything else
+            if dropped.is_some_and(|dropped| self.shows(&dropped)) {
+                self.current_match = self.current_match.and_then(|idx| idx.checked_sub(1));
             }
-            self.log.push_back(msg)
         }
+        //while scrolled back, stay on the same lines as new ones come in
+        if self.log_scroll > 0 && self.shows(&entry) {
+            self.log_scroll += 1;
+            self.unseen += 1;
+        }
+        self.log.push_back(entry);
+    }
+
+    /// whether the log pane lists `entry` with the current filter
+    pub fn shows(&self, entry: &LogEntry) -> bool {
+        let debug_ok = entry.category != LogCategory::Debug || self.show_debug;
+        let filter_ok = match self.filter {
+            LogFilter::All => true,
+            LogFilter::Only(category) => entry.category == category,
+        };
+        debug_ok && filter_ok
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &LogEntry> {
+        self.log.iter().filter(|entry| self.shows(entry))
+    }
+
+    /// whether `entry` has the search in it, ignoring case
+    pub fn is_match(&self, entry: &LogEntry) -> bool {
+        !self.search.is_empty() && entry.text.to_ascii_lowercase().contains(&self.search.to_ascii_lowercase())
+    }
 
-        /* let scroll_size = self.window_size.saturating_sub(self.window.len());
-        if self.window.len() >= self.window_size && self.log_scroll < scroll_size {
-            self.scroll_down();
-        } */
+    /// where the last match moved to is among all the matches, and how many there are
+    pub fn match_position(&self) -> (Option<usize>, usize) {
+        let matches: Vec<usize> = self.matches();
+        let position = self.current_match.and_then(|current| matches.iter().position(|idx| *idx == current));
+        (position, matches.len())
+    }
 
-        /* if self.window.len() >= self.window_size {
-            self.log_scroll = self.window.len().saturating_sub(self.window_size);
-        }  */
-        /* let max_scroll = self.window.len().saturating_sub(self.window_size);
-        if self.log_scroll >= max_scroll.saturating_sub(1) {
-            self.log_scroll = max_scroll;
-        } */    
-        let max_scroll = self.window.len().saturating_sub(self.window_size);
+    /// the filtered entries that match, oldest first
+    fn matches(&self) -> Vec<usize> {
+        self.visible()
+            .enumerate()
+            .filter(|(_, entry)| self.is_match(entry))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
 
-        // Only scroll if log_scroll == current max_scroll before adding new log
-        if self.log_scroll == max_scroll {
-            self.log_scroll = self.window.len().saturating_sub(self.window_size);
+    pub fn handle(&mut self, input: LogInput) {
+        let page = self.window_size.max(1) as isize;
+        match input {
+            LogInput::Filter => {
+                let shown: Vec<LogCategory> = LogCategory::ALL
+                    .into_iter()
+                    .filter(|category| *category != LogCategory::Debug || self.show_debug)
+                    .collect();
+                self.filter = match self.filter {
+                    LogFilter::All => LogFilter::Only(shown[0]),
+                    LogFilter::Only(category) => match shown.iter().position(|shown| *shown == category) {
+                        Some(idx) if idx + 1 < shown.len() => LogFilter::Only(shown[idx + 1]),
+                        _ => LogFilter::All,
+                    },
+                };
+                self.refilter();
+            },
+            LogInput::Debug => {
+                self.show_debug = !self.show_debug;
+                if !self.show_debug && self.filter == LogFilter::Only(LogCategory::Debug) {
+                    self.filter = LogFilter::All;
+                }
+                self.refilter();
+            },
+            LogInput::PageUp => self.scroll_by(page),
+            LogInput::PageDown => self.scroll_by(-page),
+            LogInput::Oldest => self.scroll_by(isize::MAX),
+            LogInput::Newest => self.scroll_by(isize::MIN),
+            LogInput::Search => {
+                self.search.clear();
+                self.current_match = None;
+                self.searching = true;
+            },
+            LogInput::OlderMatch => self.step_match(true),
+            LogInput::NewerMatch => self.step_match(false),
         }
+    }
 
+    /// back to the newest entry after the filter changed what is shown
+    fn refilter(&mut self) {
+        //the match was counted among the entries shown before
+        self.current_match = None;
+        self.log_scroll = 0;
+        self.update_window();
     }
 
-    pub fn set_window_size(&mut self, line_number: usize) {
-        self.window_size = line_number;
+    pub fn search_push(&mut self, c: char) {
+        self.search.push(c);
+        self.current_match = None;
+        self.step_match(true);
     }
 
-    pub fn scroll_down(&mut self) {
-        if self.log_scroll < self.window.len() {
-            self.log_scroll += 1;
-            self.update_window();
+    pub fn search_pop(&mut self) {
+        self.search.pop();
+        self.current_match = None;
+        self.step_match(true);
+    }
+
+    /// stops typing but keeps the matches highlighted, an empty search clears them
+    pub fn finish_search(&mut self) {
+        self.searching = false;
+        if self.search.is_empty() {
+            self.current_match = None;
         }
     }
 
-    pub fn scroll_up(&mut self) {
-        if self.log_scroll > 0 {
-            self.log_scroll -= 1;
-            self.update_window();
+    pub fn cancel_search(&mut self) {
+        self.searching = false;
+        self.search.clear();
+        self.current_match = None;
+    }
+
+    /// moves the view to the next match in one direction, searching starts from the newest
+    fn step_match(&mut self, older: bool) {
+        let matches = self.matches();
+        let next = match (self.current_match, older) {
+            (None, _) => matches.last(),
+            (Some(current), true) => matches.iter().rev().find(|idx| **idx < current),
+            (Some(current), false) => matches.iter().find(|idx| **idx > current),
+        };
+        if let Some(&idx) = next {
+            self.current_match = Some(idx);
+            self.show(idx);
         }
     }
 
+    /// scrolls just enough to put the entry at `idx` in the middle of the window
+    fn show(&mut self, idx: usize) {
+        let total = self.visible().count();
+        let end = (idx + 1 + self.window_size / 2).min(total);
+        self.log_scroll = (total - end).min(total.saturating_sub(self.window_size));
+        self.update_window();
+    }
+
+    /// positive towards older entries, clamped at both ends
+    fn scroll_by(&mut self, lines: isize) {
+        let max = self.visible().count().saturating_sub(self.window_size);
+        self.log_scroll = self.log_scroll.saturating_add_signed(lines).min(max);
+        self.update_window();
+    }
+
+    pub fn set_window_size(&mut self, line_number: usize) {
+        self.window_size = line_number;
+    }
+
+    /// towards older messages
+    pub fn scroll_up(&mut self) {
+        self.scroll_by(1);
+    }
+
+    /// back towards the newest message
+    pub fn scroll_down(&mut self) {
+        self.scroll_by(-1);
+    }
 
     pub fn update_window(&mut self) {
-        let total = self.log.len();
+        let total = self.visible().count();
 
         self.log_scroll = self.log_scroll.min(total);
+        if self.log_scroll == 0 {
+            self.unseen = 0;
+        }
 
         let end = total.saturating_sub(self.log_scroll);
         let start = end.saturating_sub(self.window_size);
+        self.window_start = start;
 
-        self.window = self.log
-            .iter()
+        self.window = self.visible()
             .skip(start)
             .take(end - start)
             .cloned()
             .collect();
     }
 
-    pub fn get_window(&self) -> &[String] {
+    pub fn get_window(&self) -> &[LogEntry] {
         &self.window
     }
+
+    /// where the window starts among the filtered entries
+    pub fn window_start(&self) -> usize {
+        self.window_start
+    }
+
+    /// the filtered entries, shown or not
+    pub fn visible_len(&self) -> usize {
+        self.visible().count()
+    }
+
+    /// the line of the window the last match moved to is on
+    pub fn current_in_window(&self) -> Option<usize> {
+        self.current_match?.checked_sub(self.window_start).filter(|line| *line < self.window.len())
+    }
+
+    /// `None` while following new entries, otherwise how many came in since scrolling back
+    pub fn paused(&self) -> Option<usize> {
+        (self.log_scroll > 0).then_some(self.unseen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// one entry of every kind, oldest first
+    fn logger() -> Logger {
+        let mut logger = Logger::new();
+        logger.push(LogEntry::game("You shoot the Dealer"));
+        logger.push(LogEntry::system("Switched to the Classic preset"));
+        logger.push(LogEntry::chat("Try the saw"));
+        logger.push(LogEntry::debug("dealer picked self"));
+        logger.push(LogEntry::system("Could not save the log").error());
+        logger.set_window_size(10);
+        logger.update_window();
+        logger
+    }
+
+    fn shown(logger: &Logger) -> Vec<&str> {
+        logger.get_window().iter().map(|entry| entry.text.as_str()).collect()
+    }
+
+    #[test]
+    fn debug_entries_are_hidden_until_asked_for() {
+        let mut logger = logger();
+        assert_eq!(logger.visible_len(), 4);
+        assert!(!shown(&logger).contains(&"dealer picked self"));
+        logger.handle(LogInput::Debug);
+        assert_eq!(logger.visible_len(), 5);
+        assert!(shown(&logger).contains(&"dealer picked self"));
+    }
+
+    #[test]
+    fn filter_goes_through_every_kind_then_back_to_all() {
+        let mut logger = logger();
+        let mut names = Vec::new();
+        for _ in 0..4 {
+            logger.handle(LogInput::Filter);
+            names.push(logger.filter.name());
+        }
+        assert_eq!(names, ["game", "system", "chat", "all"]);
+
+        logger.handle(LogInput::Debug);
+        for _ in 0..4 {
+            logger.handle(LogInput::Filter);
+        }
+        assert_eq!(logger.filter, LogFilter::Only(LogCategory::Debug));
+        assert_eq!(shown(&logger), ["dealer picked self"]);
+    }
+
+    #[test]
+    fn filter_keeps_warnings_and_errors_of_its_kind() {
+        let mut logger = logger();
+        logger.handle(LogInput::Filter);
+        logger.handle(LogInput::Filter);
+        assert_eq!(shown(&logger), ["Switched to the Classic preset", "Could not save the log"]);
+    }
+
+    #[test]
+    fn hiding_debug_drops_a_debug_filter() {
+        let mut logger = logger();
+        logger.handle(LogInput::Debug);
+        logger.filter = LogFilter::Only(LogCategory::Debug);
+        logger.handle(LogInput::Debug);
+        assert_eq!(logger.filter, LogFilter::All);
+        assert_eq!(logger.visible_len(), 4);
+    }
+
+    #[test]
+    fn saved_lines_carry_the_round_and_turn() {
+        let mut entry = LogEntry::game("You shoot the Dealer");
+        entry.time = Duration::from_secs(83);
+        entry.round = 2;
+        assert_eq!(entry.to_string(), "[01:23] round 2 game: You shoot the Dealer");
+        entry.turn = Some("You".to_string());
+        assert_eq!(entry.to_string(), "[01:23] round 2 (You) game: You shoot the Dealer");
+        let entry = LogEntry { severity: Severity::Warning, ..entry };
+        assert_eq!(entry.to_string(), "[01:23] round 2 (You) game warning: You shoot the Dealer");
+    }
+
+    /// twenty game entries with a shot every fifth one, in a window of four lines
+    fn long_logger() -> Logger {
+        let mut logger = Logger::new();
+        for idx in 0..20 {
+            let text = if idx % 5 == 0 { format!("{} You shoot the Dealer", idx) } else { format!("{} nothing", idx) };
+            logger.push(LogEntry::game(text));
+        }
+        logger.set_window_size(4);
+        logger.update_window();
+        logger
+    }
+
+    fn type_search(logger: &mut Logger, search: &str) {
+        logger.handle(LogInput::Search);
+        for c in search.chars() {
+            logger.search_push(c);
+        }
+    }
+
+    #[test]
+    fn search_starts_at_the_newest_match_ignoring_case() {
+        let mut logger = long_logger();
+        type_search(&mut logger, "SHOOT");
+        assert_eq!(logger.match_position(), (Some(3), 4));
+        let current = logger.current_in_window().expect("the match is scrolled into view");
+        assert_eq!(logger.get_window()[current].text, "15 You shoot the Dealer");
+    }
+
+    #[test]
+    fn search_steps_through_matches_and_stops_at_the_ends() {
+        let mut logger = long_logger();
+        type_search(&mut logger, "shoot");
+        logger.finish_search();
+        let mut positions = Vec::new();
+        for _ in 0..4 {
+            logger.handle(LogInput::OlderMatch);
+            positions.push(logger.match_position().0);
+        }
+        assert_eq!(positions, [Some(2), Some(1), Some(0), Some(0)]);
+        let current = logger.current_in_window().expect("the match is scrolled into view");
+        assert_eq!(logger.get_window()[current].text, "0 You shoot the Dealer");
+
+        logger.handle(LogInput::NewerMatch);
+        assert_eq!(logger.match_position().0, Some(1));
+    }
+
+    #[test]
+    fn search_without_matches_leaves_the_view_alone() {
+        let mut logger = long_logger();
+        type_search(&mut logger, "xyz");
+        assert_eq!(logger.match_position(), (None, 0));
+        assert_eq!(logger.paused(), None);
+        logger.cancel_search();
+        assert!(logger.search.is_empty() && !logger.searching);
+    }
+
+    #[test]
+    fn scrolled_back_the_view_stays_put_and_counts_new_entries() {
+        let mut logger = long_logger();
+        logger.handle(LogInput::PageUp);
+        assert_eq!(logger.paused(), Some(0));
+        let before = shown(&logger).join("|");
+        logger.push(LogEntry::game("new"));
+        logger.push(LogEntry::game("newer"));
+        logger.update_window();
+        assert_eq!(shown(&logger).join("|"), before);
+        assert_eq!(logger.paused(), Some(2));
+
+        logger.handle(LogInput::Newest);
+        assert_eq!(logger.paused(), None);
+        assert_eq!(shown(&logger).last(), Some(&"newer"));
+    }
+
+    #[test]
+    fn scrolling_is_clamped_at_both_ends() {
+        let mut logger = long_logger();
+        logger.handle(LogInput::Oldest);
+        assert_eq!(logger.window_start(), 0);
+        logger.scroll_up();
+        assert_eq!(logger.window_start(), 0);
+        logger.handle(LogInput::Newest);
+        logger.scroll_down();
+        assert_eq!(logger.window_start(), 16);
+    }
 }
@@ -1,4 +1,15 @@
 pub mod widget_data;
+pub mod panes;
 pub mod logger;
 pub mod colors;
 pub mod grid;
+pub mod console;
+pub mod inspector;
+pub mod settings;
+pub mod inventory;
+pub mod ui_help;
+pub mod history;
+pub mod palette;
+pub mod hit_map;
+pub mod windows;
+pub mod dialog;
@@ -0,0 +1,88 @@
+//palette.rs
+//the ':' command line at the bottom of the screen, what it understands lives in components/commands.rs
+use crate::ui_components::history::History;
+
+#[derive(Debug, Default)]
+pub struct Palette {
+    pub open: bool,
+    pub input: String,
+    /// the last error, shown until the next key
+    pub error: Option<String>,
+    /// what tab could complete the word being typed to
+    pub candidates: Vec<String>,
+    history: History,
+}
+
+impl Palette {
+    pub fn new() -> Self {
+        Palette {
+            open: false,
+            input: String::new(),
+            error: None,
+            candidates: Vec::new(),
+            history: History::new(),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.input.clear();
+        self.error = None;
+        self.candidates.clear();
+        self.history.reset();
+    }
+
+    pub fn push_char(&mut self, c: char) {
+        self.input.push(c);
+        self.error = None;
+        self.candidates.clear();
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+        self.error = None;
+        self.candidates.clear();
+    }
+
+    /// takes the typed line and remembers it, the palette closes once it runs
+    pub fn submit(&mut self) -> Option<String> {
+        let line = self.input.trim().to_string();
+        if line.is_empty() {
+            return None;
+        }
+        self.history.push(line.clone());
+        Some(line)
+    }
+
+    /// fills in the word being typed from `candidates`, as far as they agree
+    pub fn complete(&mut self, candidates: Vec<String>) {
+        let start = self.input.rfind(' ').map_or(0, |idx| idx + 1);
+        match candidates.as_slice() {
+            [] => {},
+            [only] => {
+                self.input.truncate(start);
+                self.input.push_str(only);
+                self.input.push(' ');
+            },
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |len, candidate| {
+                    first.chars().zip(candidate.chars()).take(len).take_while(|(a, b)| a == b).count()
+                });
+                let prefix: String = first.chars().take(common).collect();
+                self.input.truncate(start);
+                self.input.push_str(&prefix);
+            },
+        }
+        self.candidates = if candidates.len() > 1 { candidates } else { Vec::new() };
+    }
+
+    pub fn history_prev(&mut self) {
+        if let Some(line) = self.history.back() {
+            self.input = line.to_string();
+        }
+    }
+
+    pub fn history_next(&mut self) {
+        self.input = self.history.forward().map(str::to_string).unwrap_or_default();
+    }
+}
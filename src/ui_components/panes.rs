@@ -0,0 +1,335 @@
+//panes.rs
+//the windows that can be opened over the table, each one says where it opens and how it draws
+use ratatui::{
+    layout::Rect, prelude::*, style::{Color, Styled}, widgets::{BorderType, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap}
+};
+
+use crate::app::App;
+use crate::data::PLAYER;
+use crate::rules::MAX_INVENTORY_SIZE;
+use crate::event::AppEvent;
+use crate::keymap::Context;
+use crate::ui::{self, PLAYER_ART};
+use crate::ui_components::grid::{self, Grid, GridCell};
+use crate::ui_components::hit_map::HitTarget;
+use crate::ui_components::inspector::{self, InspectorInput};
+use crate::ui_components::inventory::{self, SLOT_COLS};
+use crate::ui_components::logger::{LogCategory, LogEntry, Severity};
+use crate::ui_components::widget_data::{Pane, WidgetKind};
+
+/// every built-in pane, tab goes through them in this order
+pub fn all() -> Vec<Box<dyn Pane>> {
+    vec![
+        Box::new(LogPane),
+        Box::new(DataPane),
+        Box::new(InventoryPane),
+        Box::new(PlayerPane),
+    ]
+}
+
+#[derive(Debug)]
+pub struct LogPane;
+
+impl Pane for LogPane {
+    fn kind(&self) -> WidgetKind {
+        WidgetKind::Log
+    }
+
+    fn title(&self, app: &App) -> String {
+        let debug = if app.logger.show_debug { " + debug" } else { "" };
+        let follow = match app.logger.paused() {
+            None => "following".to_string(),
+            Some(0) => "paused".to_string(),
+            Some(unseen) => format!("paused, {} new", unseen),
+        };
+        format!("Message Log - {}{} - {}", app.logger.filter.name(), debug, follow)
+    }
+
+    fn default_area(&self, bounds: Rect) -> Rect {
+        let width = (bounds.width as f32 * 0.33) as u16;
+        let height = (bounds.height as f32 * 0.75) as u16;
+        Rect {
+            x: bounds.x + (bounds.width.saturating_sub(width)) / 2,
+            y: bounds.y + (bounds.height.saturating_sub(height)) / 2,
+            width,
+            height,
+        }
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let logger = &app.logger;
+        let current = logger.current_in_window();
+        let lines: Vec<Line> = logger.get_window()
+            .iter()
+            .enumerate()
+            .map(|(line, entry)| {
+                let stamp = match &entry.turn {
+                    Some(turn) => format!("R{} {} ", entry.round, turn),
+                    None => format!("R{} ", entry.round),
+                };
+                let mut spans = vec![Span::styled(stamp, Style::default().fg(Color::DarkGray))];
+                let style = Style::default().fg(log_color(entry));
+                if logger.is_match(entry) {
+                    let found = if current == Some(line) { Color::LightRed } else { Color::Yellow };
+                    spans.extend(highlight(&entry.text, &logger.search, style, style.fg(Color::Black).bg(found)));
+                } else {
+                    spans.push(Span::styled(entry.text.clone(), style));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        let mut block = ui::pane_block(app, self);
+        if logger.searching || !logger.search.is_empty() {
+            let prompt = match logger.match_position() {
+                _ if logger.searching => format!("/{}_", logger.search),
+                (_, 0) => format!("/{} - no matches", logger.search),
+                (Some(position), total) => format!("/{} - {} of {}, n b for more", logger.search, position + 1, total),
+                (None, total) => format!("/{} - {} matches, n b to go through", logger.search, total),
+            };
+            block = block.title_bottom(prompt);
+        }
+        let log_popup = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap {trim: true});
+        frame.render_widget(log_popup, area);
+
+        //only once there is more than fits
+        let total = logger.visible_len();
+        let shown = logger.get_window().len();
+        if total > shown {
+            let mut state = ScrollbarState::new(total - shown + 1)
+                .viewport_content_length(shown)
+                .position(logger.window_start());
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None);
+            frame.render_stateful_widget(scrollbar, area.inner(Margin { vertical: 1, horizontal: 0 }), &mut state);
+        }
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Log)
+    }
+
+    fn scroll(&self, up: bool) -> Option<AppEvent> {
+        Some(if up { AppEvent::ScrollUp } else { AppEvent::ScrollDown })
+    }
+}
+
+/// `text` with every occurrence of `search` in `found`, ignoring case
+fn highlight(text: &str, search: &str, style: Style, found: Style) -> Vec<Span<'static>> {
+    let lower = text.to_ascii_lowercase();
+    let search = search.to_ascii_lowercase();
+    let mut spans = Vec::new();
+    let mut rest = 0;
+    for (start, _) in lower.match_indices(&search) {
+        spans.push(Span::styled(text[rest..start].to_string(), style));
+        spans.push(Span::styled(text[start..start + search.len()].to_string(), found));
+        rest = start + search.len();
+    }
+    spans.push(Span::styled(text[rest..].to_string(), style));
+    spans
+}
+
+/// warnings and errors stand out whatever they are about
+fn log_color(entry: &LogEntry) -> Color {
+    match (entry.severity, entry.category) {
+        (Severity::Error, _) => Color::Red,
+        (Severity::Warning, _) => Color::Yellow,
+        (Severity::Info, LogCategory::Game) => Color::White,
+        (Severity::Info, LogCategory::System) => Color::Cyan,
+        (Severity::Info, LogCategory::Chat) => Color::LightGreen,
+        (Severity::Info, LogCategory::Debug) => Color::DarkGray,
+    }
+}
+
+#[derive(Debug)]
+pub struct DataPane;
+
+impl Pane for DataPane {
+    fn kind(&self) -> WidgetKind {
+        WidgetKind::Data
+    }
+
+    fn title(&self, app: &App) -> String {
+        let title = if app.debug { "Data - debug" } else { "Data - arrows to browse" };
+        title.to_string()
+    }
+
+    fn default_area(&self, bounds: Rect) -> Rect {
+        Rect {
+            x: 1,
+            y: 2,
+            width: bounds.width * 40 / 100,
+            height: bounds.height * 50 / 100,
+        }
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let focused = app.widget_data.is_focused(WidgetKind::Data);
+        let tree = inspector::build_tree(app);
+        let rows = app.inspector.rows(&tree);
+        let cursor = app.inspector.cursor.min(rows.len().saturating_sub(1));
+
+        //keep the cursor on screen once the tree is taller than the popup
+        let visible = area.height.saturating_sub(2) as usize;
+        let skip = cursor.saturating_sub(visible.saturating_sub(1));
+        let lines: Vec<Line> = rows.iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(idx, row)| {
+                let marker = match (row.has_children, row.expanded) {
+                    (true, true) => "▾ ",
+                    (true, false) => "▸ ",
+                    (false, _) => "  ",
+                };
+                let text = format!("{}{}{}", "  ".repeat(row.depth), marker, row.label);
+                if focused && idx == cursor {
+                    Line::from(Span::styled(text, Style::default().fg(Color::Black).bg(Color::LightRed)))
+                } else {
+                    Line::from(text)
+                }
+            })
+            .collect();
+
+        let data_popup = Paragraph::new(lines)
+            .block(ui::pane_block(app, self))
+            .style(Style::default()
+                .fg(app.widget_data.get_color(&WidgetKind::Data).unwrap_or(Color::White))
+            );
+        frame.render_widget(data_popup, area);
+    }
+
+    fn color(&self) -> Option<Color> {
+        Some(Color::White)
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Data)
+    }
+
+    fn scroll(&self, up: bool) -> Option<AppEvent> {
+        Some(AppEvent::Inspect(if up { InspectorInput::Up } else { InspectorInput::Down }))
+    }
+}
+
+//the player's item slots with a tooltip for the selected one, docked above the status bar
+#[derive(Debug)]
+pub struct InventoryPane;
+
+impl Pane for InventoryPane {
+    fn kind(&self) -> WidgetKind {
+        WidgetKind::Inventory
+    }
+
+    fn title(&self, app: &App) -> String {
+        let keys = match app.data.rules.inventory_size {
+            0 | 1 => "1".to_string(),
+            size => format!("1-{}", size),
+        };
+        format!("Inventory - {} or enter to use, arrows to select", keys)
+    }
+
+    fn default_area(&self, bounds: Rect) -> Rect {
+        //room for the biggest inventory the rules allow
+        let width = (SLOT_COLS as u16 * 18 + 2).min(bounds.width.saturating_sub(2));
+        let height = (inventory::slot_rows(MAX_INVENTORY_SIZE) as u16 * 4 + 5).min(bounds.height.saturating_sub(4));
+        Rect {
+            x: bounds.x + 1,
+            y: bounds.bottom().saturating_sub(height + 3),
+            width,
+            height,
+        }
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        let items = app.data.players[PLAYER].items();
+        let cells = items.iter()
+            .map(|item| GridCell {
+                glyph: item.glyph().to_string(),
+                label: item.name().to_string(),
+                style: Style::default().fg(ui::rarity_color(item.rarity())),
+            })
+            .collect();
+        let focused = app.widget_data.is_focused(WidgetKind::Inventory);
+        let size = app.data.rules.inventory_size;
+        let rows = inventory::slot_rows(size);
+        let grid = Grid {
+            cols: SLOT_COLS,
+            rows,
+            slots: size,
+            cells,
+            selected: focused.then_some(app.inventory.cursor),
+        };
+
+        let tooltip = match items.get(app.inventory.cursor) {
+            Some(item) => item.describe(),
+            None => "Empty slot.",
+        };
+
+        let block = ui::pane_block(app, self).border_type(BorderType::Rounded);
+        let inner = block.inner(area);
+        let [grid_area, tooltip_area] = Layout::vertical([
+            Constraint::Length(rows as u16 * 4),
+            Constraint::Min(0),
+        ]).areas(inner);
+
+        frame.render_widget(block, area);
+        frame.render_widget(grid, grid_area);
+        frame.render_widget(Paragraph::new(tooltip).wrap(Wrap { trim: true }), tooltip_area);
+
+        let mut hit_map = app.hit_map.borrow_mut();
+        for (slot, slot_area) in grid::slot_areas(SLOT_COLS, rows, grid_area).into_iter().take(size).enumerate() {
+            hit_map.add(slot_area, HitTarget::InventorySlot(slot));
+        }
+    }
+
+    fn context(&self) -> Option<Context> {
+        Some(Context::Inventory)
+    }
+}
+
+#[derive(Debug)]
+pub struct PlayerPane;
+
+impl Pane for PlayerPane {
+    fn kind(&self) -> WidgetKind {
+        WidgetKind::Player
+    }
+
+    fn title(&self, _app: &App) -> String {
+        "Popup".to_string()
+    }
+
+    fn default_area(&self, bounds: Rect) -> Rect {
+        Rect {
+            x: 10,
+            y: 5,
+            width: 20,
+            height: 10,
+        }.intersection(bounds)
+    }
+
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect) {
+        // The "icon" — can be emoji, unicode, ASCII art, etc.
+        let mut lines: Vec<Line> = PLAYER_ART.lines().map(Line::from).collect();
+        for player in &app.data.players {
+            let items = if player.items().is_empty() {
+                "no items".to_string()
+            } else {
+                format!("{} items", player.items().len())
+            };
+            lines.push(Line::from(format!("{}: {} hp, {}", player.name(), player.health(), items)));
+            if !player.statuses().is_empty() {
+                lines.push(Line::from(ui::status_spans(player)));
+            }
+        }
+
+        //no border to turn red, the text does instead
+        let mut player_popup = Paragraph::new(lines)
+            .block(ui::pane_block(app, self).borders(Borders::NONE));
+        if app.widget_data.is_focused(WidgetKind::Player) {
+            player_popup = player_popup.set_style(Style::default().fg(Color::LightRed))
+        }
+        frame.render_widget(player_popup, area);
+    }
+}
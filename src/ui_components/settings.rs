@@ -0,0 +1,135 @@
+//settings.rs
+//the settings popup: pick a preset to play, or tweak one and save it as your own
+use crate::components::shotgun::ShotgunModel;
+use crate::presets::{Preset, ShellSource};
+use crate::rules::MAX_STARTING_HEALTH;
+
+/// keys the settings popup reacts to
+#[derive(Debug, Clone, Copy)]
+pub enum SettingsInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// enter, play the selected preset or the draft
+    Select,
+    /// save the draft as a new custom preset
+    Save,
+}
+
+/// what the app has to do after a key in the settings popup
+#[derive(Debug, Clone)]
+pub enum SettingsAction {
+    Play(Preset),
+    /// ask for a name to save the draft under, starting from this one
+    Name(String),
+}
+
+/// the draft rows shown under the preset list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsRow {
+    Health,
+    Shells,
+    Model,
+    Log,
+    Odds,
+}
+
+impl SettingsRow {
+    pub const ALL: [SettingsRow; 5] = [
+        SettingsRow::Health,
+        SettingsRow::Shells,
+        SettingsRow::Model,
+        SettingsRow::Log,
+        SettingsRow::Odds,
+    ];
+}
+
+#[derive(Debug, Default)]
+pub struct Settings {
+    /// over the presets first, then the draft rows
+    pub cursor: usize,
+    /// copy of the last preset picked, edited by the rows below the list
+    pub draft: Preset,
+}
+
+impl Settings {
+    pub fn new(current: &Preset) -> Self {
+        Settings {
+            cursor: 0,
+            draft: current.clone(),
+        }
+    }
+
+    /// the draft row under the cursor, `None` while it is on a preset
+    pub fn selected_row(&self, presets: &[Preset]) -> Option<SettingsRow> {
+        self.cursor.checked_sub(presets.len()).map(|idx| SettingsRow::ALL[idx])
+    }
+
+    pub fn handle(&mut self, input: SettingsInput, presets: &[Preset]) -> Option<SettingsAction> {
+        let rows = presets.len() + SettingsRow::ALL.len();
+        match input {
+            SettingsInput::Up => self.cursor = (self.cursor + rows - 1) % rows,
+            SettingsInput::Down => self.cursor = (self.cursor + 1) % rows,
+            SettingsInput::Left => self.edit(presets, false),
+            SettingsInput::Right => self.edit(presets, true),
+            SettingsInput::Select => {
+                let preset = match presets.get(self.cursor) {
+                    Some(preset) => preset.clone(),
+                    None => self.draft.clone(),
+                };
+                self.draft = preset.clone();
+                return Some(SettingsAction::Play(preset));
+            },
+            SettingsInput::Save => {
+                let taken = |name: &str| presets.iter().any(|preset| preset.name == name);
+                let name = (1..)
+                    .map(|n| format!("Custom {}", n))
+                    .find(|name| !taken(name))
+                    .unwrap_or_default();
+                return Some(SettingsAction::Name(name));
+            },
+        }
+        None
+    }
+
+    /// names the draft and turns it into a custom preset, the error says why the name won't do
+    pub fn save_as(&mut self, name: &str, presets: &[Preset]) -> Result<Preset, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A preset needs a name".to_string());
+        }
+        if presets.iter().any(|preset| preset.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("There already is a preset called {}", name));
+        }
+        self.draft.name = name.to_string();
+        self.draft.custom = true;
+        Ok(self.draft.clone())
+    }
+
+    fn edit(&mut self, presets: &[Preset], forward: bool) {
+        let cycle = |idx: usize, len: usize| if forward { (idx + 1) % len } else { (idx + len - 1) % len };
+        match self.selected_row(presets) {
+            Some(SettingsRow::Health) => {
+                let health = &mut self.draft.rules.starting_health;
+                *health = if forward { (*health + 1).min(MAX_STARTING_HEALTH) } else { health.saturating_sub(1).max(1) };
+            },
+            Some(SettingsRow::Shells) => {
+                let idx = ShellSource::ALL.iter().position(|source| *source == self.draft.shells).unwrap_or(0);
+                self.draft.shells = ShellSource::ALL[cycle(idx, ShellSource::ALL.len())];
+            },
+            Some(SettingsRow::Model) => {
+                let idx = ShotgunModel::ALL.iter().position(|model| *model == self.draft.model).unwrap_or(0);
+                self.draft.model = ShotgunModel::ALL[cycle(idx, ShotgunModel::ALL.len())];
+            },
+            Some(SettingsRow::Log) => self.draft.log = !self.draft.log,
+            Some(SettingsRow::Odds) => self.draft.odds = !self.draft.odds,
+            //left and right on a preset copy it into the draft
+            None => {
+                if let Some(preset) = presets.get(self.cursor) {
+                    self.draft = preset.clone();
+                }
+            },
+        }
+    }
+}
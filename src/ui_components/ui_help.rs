@@ -0,0 +1,137 @@
+//ui_help.rs
+//the '?' overlay: the key reference comes straight from the keymap, the rest from the game's own descriptions
+use ratatui::prelude::*;
+
+use crate::components::items::Items;
+use crate::components::shotgun::{Shell, ShotgunModel, ShotgunState};
+use crate::keymap::{Context, Keymap};
+
+/// keys the help overlay reacts to
+#[derive(Debug, Clone, Copy)]
+pub enum HelpInput {
+    Next,
+    Prev,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HelpPage {
+    Keys,
+    Shells,
+    Items,
+    States,
+    Models,
+}
+
+impl HelpPage {
+    pub const ALL: [HelpPage; 5] = [
+        HelpPage::Keys,
+        HelpPage::Shells,
+        HelpPage::Items,
+        HelpPage::States,
+        HelpPage::Models,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            HelpPage::Keys => "Keys",
+            HelpPage::Shells => "Shells",
+            HelpPage::Items => "Items",
+            HelpPage::States => "Shotgun states",
+            HelpPage::Models => "Shotgun models",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Help {
+    pub open: bool,
+    pub page: usize,
+    pub scroll: u16,
+}
+
+impl Help {
+    pub fn new() -> Self {
+        Help { open: false, page: 0, scroll: 0 }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.scroll = 0;
+    }
+
+    pub fn page(&self) -> HelpPage {
+        HelpPage::ALL[self.page]
+    }
+
+    pub fn handle(&mut self, input: HelpInput) {
+        let pages = HelpPage::ALL.len();
+        match input {
+            HelpInput::Next => {
+                self.page = (self.page + 1) % pages;
+                self.scroll = 0;
+            },
+            HelpInput::Prev => {
+                self.page = (self.page + pages - 1) % pages;
+                self.scroll = 0;
+            },
+            HelpInput::Up => self.scroll = self.scroll.saturating_sub(1),
+            HelpInput::Down => self.scroll = self.scroll.saturating_add(1),
+        }
+    }
+}
+
+/// the text of a page, `active` contexts come first on the key page
+pub fn page_lines(page: HelpPage, keymap: &Keymap, active: &[Context]) -> Vec<Line<'static>> {
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+    match page {
+        HelpPage::Keys => {
+            let mut contexts = Context::ALL.to_vec();
+            contexts.sort_by_key(|context| !active.contains(context));
+            for context in contexts {
+                let bindings = keymap.describe(context);
+                if bindings.is_empty() {
+                    continue;
+                }
+                if active.contains(&context) {
+                    lines.push(Line::from(vec![
+                        Span::styled(context.title(), heading.fg(Color::LightRed)),
+                        Span::styled(" - active", Style::default().fg(Color::DarkGray)),
+                    ]));
+                } else {
+                    lines.push(Line::from(Span::styled(context.title(), heading)));
+                }
+                for (keys, description) in bindings {
+                    lines.push(Line::from(format!("  {:<18} {}", keys, description)));
+                }
+                lines.push(Line::from(""));
+            }
+        },
+        HelpPage::Shells => {
+            for shell in Shell::ALL {
+                lines.push(Line::from(shell.describe()));
+            }
+        },
+        HelpPage::Items => {
+            for item in Items::ALL {
+                lines.push(Line::from(vec![
+                    Span::raw(item.describe()),
+                    Span::styled(format!(" {:?}", item.rarity()), Style::default().fg(Color::DarkGray)),
+                ]));
+            }
+        },
+        HelpPage::States => {
+            for state in ShotgunState::ALL {
+                lines.push(Line::from(state.describe()));
+            }
+        },
+        HelpPage::Models => {
+            for model in ShotgunModel::ALL {
+                lines.push(Line::from(model.describe()));
+            }
+        },
+    }
+    lines
+}
@@ -1,8 +1,17 @@
 //widget-data.rs
+use std::fmt;
+
 use ratatui::layout::Rect;
-use crate::ui::{SHOTGUN_ART, BANG, CLICK};
+use ratatui::Frame;
+use crate::ui::SHOTGUN_ART;
+
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::style::Color;
 
-use ratatui::style::{Color, Style, Stylize};
+use crate::app::App;
+use crate::event::AppEvent;
+use crate::keymap::Context;
+use crate::ui_components::panes;
 
 #[derive(Debug, Clone)]
 pub struct WidgetState {
@@ -37,8 +46,8 @@ impl WidgetState {
 
     pub fn new_color(color: Option<Color>) -> WidgetState{
         WidgetState {
-            display: true,
-            focus: true,
+            display: false,
+            focus: false,
             area: None,
             content: None,
             color,
@@ -48,6 +57,19 @@ impl WidgetState {
     pub fn toggle_focus(&mut self) {
         self.focus = !self.focus;
     }
+
+    /// fixed area to render into, `None` lets the widget place itself
+    pub fn area(&self) -> Option<Rect> {
+        self.area
+    }
+
+    pub fn set_area(&mut self, area: Option<Rect>) {
+        self.area = area;
+    }
+
+    pub fn content(&self) -> Option<&str> {
+        self.content.as_deref()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -57,19 +79,72 @@ pub enum WidgetKind {
     Inventory,
     Player,
     Shotgun,
-    Confirmation,
+    /// a pane added with [`WidgetData::register`] that isn't one of the built-ins, by its name
+    Custom(&'static str),
+}
+
+impl WidgetKind {
+    /// the table name in the windows file
+    pub fn name(&self) -> &'static str {
+        match self {
+            WidgetKind::Log => "log",
+            WidgetKind::Data => "data",
+            WidgetKind::Inventory => "inventory",
+            WidgetKind::Player => "player",
+            WidgetKind::Shotgun => "shotgun",
+            WidgetKind::Custom(name) => name,
+        }
+    }
+}
+
+/// a window over the table that can be shown, focused, moved and resized,
+/// registering one with [`WidgetData::register`] is all it takes to add a new one
+pub trait Pane: fmt::Debug {
+    fn kind(&self) -> WidgetKind;
+
+    fn title(&self, app: &App) -> String;
+
+    /// where it opens in a terminal of `bounds`, until the player moves it
+    fn default_area(&self, bounds: Rect) -> Rect;
+
+    /// draws everything inside `area`, which is already cleared
+    fn render(&self, app: &App, frame: &mut Frame, area: Rect);
+
+    /// the color it is registered with, `None` leaves it to the terminal
+    fn color(&self) -> Option<Color> {
+        None
+    }
+
+    /// the keymap section for its keys, listed in the help and the palette
+    fn context(&self) -> Option<Context> {
+        None
+    }
+
+    /// what a key does while it has focus, ahead of the window's and the table's keys,
+    /// by default whatever its context binds the key to
+    fn handle(&self, app: &App, key_event: &KeyEvent) -> Option<AppEvent> {
+        let context = self.context()?;
+        app.keymap.action(&[context], key_event).map(|action| action.event(app))
+    }
+
+    /// what the mouse wheel does over it
+    fn scroll(&self, _up: bool) -> Option<AppEvent> {
+        None
+    }
+}
+
+#[derive(Debug)]
+struct Widget {
+    kind: WidgetKind,
+    state: WidgetState,
+    /// `None` for the ones the table draws itself, like the shotgun
+    pane: Option<Box<dyn Pane>>,
 }
 
 #[derive(Debug)]
 pub struct WidgetData{
-    //these are a little redundant
-    log: WidgetState,
-    data: WidgetState,
-    inventory: WidgetState,
-    player: WidgetState,
-    shotgun: WidgetState,
-    
-    confirmation: WidgetState,
+    //in registration order, which is also the order tab goes through them
+    widgets: Vec<Widget>,
 
     current_focus: Option<WidgetKind>,
 
@@ -77,32 +152,54 @@ pub struct WidgetData{
     pub render_stack: Vec<WidgetKind>,
 }
 
+impl Default for WidgetData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WidgetData {
     pub fn new() -> WidgetData {
-        WidgetData {
-            log: WidgetState::new_blank(),
-            data: WidgetState::new_color(Some(Color::White)),
-            inventory: WidgetState::new_blank(),
-            player: WidgetState::new_blank(),
-            shotgun: WidgetState::new_content(SHOTGUN_ART),
-
-            confirmation: WidgetState::new_blank(),
+        let mut widget_data = WidgetData {
+            widgets: Vec::new(),
             current_focus: None,
-
             render_stack: Vec::new(),
+        };
+        for pane in panes::all() {
+            widget_data.register(pane);
         }
+        widget_data.widgets.push(Widget {
+            kind: WidgetKind::Shotgun,
+            state: WidgetState::new_content(SHOTGUN_ART),
+            pane: None,
+        });
+        widget_data
+    }
+
+    /// adds a pane, replacing any registered for the same kind
+    pub fn register(&mut self, pane: Box<dyn Pane>) {
+        let kind = pane.kind();
+        self.widgets.retain(|widget| widget.kind != kind);
+        self.widgets.push(Widget {
+            kind,
+            state: WidgetState::new_color(pane.color()),
+            pane: Some(pane),
+        });
+    }
+
+    pub fn pane(&self, kind: WidgetKind) -> Option<&dyn Pane> {
+        self.widgets
+            .iter()
+            .find(|widget| widget.kind == kind)
+            .and_then(|widget| widget.pane.as_deref())
+    }
+
+    pub fn panes(&self) -> impl Iterator<Item = &dyn Pane> {
+        self.widgets.iter().filter_map(|widget| widget.pane.as_deref())
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (WidgetKind, &WidgetState)> {
-        [
-            (WidgetKind::Log, &self.log),
-            (WidgetKind::Data, &self.data),
-            (WidgetKind::Inventory, &self.inventory),
-            (WidgetKind::Player, &self.player),
-            (WidgetKind::Shotgun, &self.shotgun),
-            (WidgetKind::Confirmation, &self.confirmation),
-        ]
-            .into_iter()
+        self.widgets.iter().map(|widget| (widget.kind, &widget.state))
     }
 
     pub fn shown_widgets(&self) -> Option<WidgetKind> {
@@ -111,41 +208,29 @@ impl WidgetData {
             .map(|(kind, _)| kind)
     }
 
-    fn order() -> [WidgetKind; 5] {
-        [
-            WidgetKind::Log,
-            WidgetKind::Data,
-            WidgetKind::Inventory,
-            WidgetKind::Player,
-            WidgetKind::Shotgun,
-        ]
+    fn order(&self) -> Vec<WidgetKind> {
+        self.widgets.iter().map(|widget| widget.kind).collect()
     }
 
     fn get(&self, kind: WidgetKind) -> &WidgetState {
-        match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-            WidgetKind::Confirmation => &self.confirmation,
-        }
+        self.widgets
+            .iter()
+            .find(|widget| widget.kind == kind)
+            .map(|widget| &widget.state)
+            .expect("every widget kind is registered")
     }
 
     fn get_mut(&mut self, kind: WidgetKind) -> &mut WidgetState {
-        match kind {
-            WidgetKind::Log => &mut self.log,
-            WidgetKind::Data => &mut self.data,
-            WidgetKind::Inventory => &mut self.inventory,
-            WidgetKind::Player => &mut self.player,
-            WidgetKind::Shotgun => &mut self.shotgun,
-            WidgetKind::Confirmation => &mut self.confirmation,
-        }
+        self.widgets
+            .iter_mut()
+            .find(|widget| widget.kind == kind)
+            .map(|widget| &mut widget.state)
+            .expect("every widget kind is registered")
     }
 
     ///TODO: I want to understand this code
     pub fn focus_next(&mut self) {
-        let order = Self::order();
+        let order = self.order();
 
         let log_displayed = self.get(WidgetKind::Log).display;
 
@@ -174,6 +259,7 @@ impl WidgetData {
 
             if self.get(kind).display {
                 self.get_mut(kind).focus = true;
+                self.raise(kind);
                 return;
             }
 
@@ -182,14 +268,14 @@ impl WidgetData {
     }
 
     pub fn focus_prev(&mut self) {
-        let order = Self::order();
+        let order = self.order();
 
         // Find current focus index
         let current_idx = order.iter().position(|&kind| self.get(kind).focus);
 
         // Clear all focus
-        for kind in order {
-            self.get_mut(kind).focus = false;
+        for kind in order.iter() {
+            self.get_mut(*kind).focus = false;
         }
 
         // Start searching from the previous index
@@ -203,6 +289,7 @@ impl WidgetData {
         for _ in 0..order.len() {
             if self.get(order[prev_idx]).display {
                 self.get_mut(order[prev_idx]).focus = true;
+                self.raise(order[prev_idx]);
                 return;
             }
             prev_idx = if prev_idx == 0 { order.len() - 1 } else { prev_idx - 1 };
@@ -210,15 +297,7 @@ impl WidgetData {
     }
 
     pub fn is_displayed(&self, kind: WidgetKind) -> bool{
-        let widget_state = match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-            WidgetKind::Confirmation => &self.confirmation,
-        };
-        widget_state.display
+        self.get(kind).display
     }
 
     pub fn is_focused(&self, kind: WidgetKind) -> bool{
@@ -226,14 +305,7 @@ impl WidgetData {
     }
 
     pub fn toggle_focus(&mut self, kind: WidgetKind) {
-        match kind {
-            WidgetKind::Log => self.log.focus = !self.log.focus,
-            WidgetKind::Data => self.data.focus = !self.data.focus,
-            WidgetKind::Inventory => self.inventory.focus = !self.inventory.focus,
-            WidgetKind::Player => self.player.focus = !self.player.focus,
-            WidgetKind::Shotgun => self.shotgun.focus = !self.shotgun.focus,
-            WidgetKind::Confirmation => self.confirmation.focus = !self.confirmation.focus,
-        }
+        self.get_mut(kind).toggle_focus();
 
         if self.current_focus == Some(kind) {
             self.current_focus = None;
@@ -244,33 +316,15 @@ impl WidgetData {
     }
 
     pub fn get_state(&self, kind: WidgetKind) -> &WidgetState {
-        match kind {
-            WidgetKind::Log => &self.log,
-            WidgetKind::Data => &self.data,
-            WidgetKind::Inventory => &self.inventory,
-            WidgetKind::Player => &self.player,
-            WidgetKind::Shotgun => &self.shotgun,
-            WidgetKind::Confirmation => &self.confirmation,
-        }
+        self.get(kind)
     }
 
     pub fn set_widget(&mut self, kind: WidgetKind, display_b: bool, focus_b: bool) {
         if focus_b {
-            self.log.focus = false;
-            self.data.focus = false;
-            self.inventory.focus = false;
-            self.player.focus = false;
-            self.shotgun.focus = false;
+            self.remove_focus();
         }
 
-        let widget_to_modify = match kind {
-            WidgetKind::Log => &mut self.log,
-            WidgetKind::Data => &mut self.data,
-            WidgetKind::Inventory => &mut self.inventory,
-            WidgetKind::Player => &mut self.player,
-            WidgetKind::Shotgun => &mut self.shotgun,
-            WidgetKind::Confirmation => &mut self.confirmation,
-        };
+        let widget_to_modify = self.get_mut(kind);
         widget_to_modify.display = display_b;
         widget_to_modify.focus = focus_b;
         if focus_b {
@@ -278,37 +332,47 @@ impl WidgetData {
         }
     }
 
+    /// shows a pane on top with focus, or hides it and hands the focus to the one below
+    pub fn toggle_shown(&mut self, kind: WidgetKind) {
+        if self.is_displayed(kind) {
+            self.set_widget(kind, false, false);
+            self.render_stack.retain(|k| *k != kind);
+            if let Some(top) = self.render_stack.last().copied() {
+                self.kind_focus(&top);
+            }
+        } else {
+            self.set_widget(kind, true, true);
+            self.render_stack.push(kind);
+        }
+    }
+
     //TODO: edit this function so that it works correctly with edge cases
     pub fn remove_focus(&mut self) {
-        self.log.focus = false;
-        self.data.focus = false;
-        self.inventory.focus = false;
-        self.player.focus = false;
-        self.shotgun.focus = false;
-        self.confirmation.focus = false;
+        for widget in self.widgets.iter_mut() {
+            widget.state.focus = false;
+        }
     }
 
     pub fn kind_focus(&mut self, kind: &WidgetKind){
         self.remove_focus();
+        self.get_mut(*kind).focus = true;
+        self.raise(*kind);
+    }
 
-        match kind {
-            WidgetKind::Log => self.log.focus = true,
-            WidgetKind::Data => self.data.focus = true,
-            WidgetKind::Inventory => self.inventory.focus = true,
-            WidgetKind::Player => self.player.focus = true,
-            WidgetKind::Shotgun => self.shotgun.focus = true,
-            WidgetKind::Confirmation => self.confirmation.focus = true,
+    /// draws a shown window on top of the others
+    pub fn raise(&mut self, kind: WidgetKind) {
+        if self.render_stack.contains(&kind) {
+            self.render_stack.retain(|k| *k != kind);
+            self.render_stack.push(kind);
         }
     }
 
+    /// a fixed place for the window, `None` puts it back where it opens
+    pub fn set_area(&mut self, kind: WidgetKind, area: Option<Rect>) {
+        self.get_mut(kind).set_area(area);
+    }
+
     pub fn get_color(&self, kind: &WidgetKind) -> Option<Color> {
-        match kind {
-            WidgetKind::Log => self.log.color,
-            WidgetKind::Data => self.data.color,
-            WidgetKind::Inventory => self.inventory.color,
-            WidgetKind::Shotgun => self.shotgun.color,
-            WidgetKind::Player => self.player.color,
-            WidgetKind::Confirmation => self.confirmation.color,
-        }
+        self.get(*kind).color
     }
 }
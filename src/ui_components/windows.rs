@@ -0,0 +1,239 @@
+//windows.rs
+//moving and resizing the panes, and remembering where they were left between sessions
+use std::collections::BTreeMap;
+use std::fs;
+
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::config::config_dir;
+use crate::ui_components::widget_data::{WidgetData, WidgetKind};
+
+const WINDOWS_FILE: &str = "windows.toml";
+
+/// smallest a window can be resized to, borders included
+pub const MIN_WIDTH: u16 = 12;
+pub const MIN_HEIGHT: u16 = 4;
+
+/// columns and rows a key moves or resizes a window by
+const STEP_X: i32 = 2;
+const STEP_Y: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowInput {
+    Left,
+    Right,
+    Up,
+    Down,
+    Wider,
+    Narrower,
+    Taller,
+    Shorter,
+    /// back to where the window opens by default
+    Reset,
+}
+
+impl WindowInput {
+    /// where a window drawn at `area` ends up, `None` for its default place
+    pub fn apply(self, area: Rect, bounds: Rect) -> Option<Rect> {
+        let (dx, dy, dw, dh) = match self {
+            WindowInput::Left => (-STEP_X, 0, 0, 0),
+            WindowInput::Right => (STEP_X, 0, 0, 0),
+            WindowInput::Up => (0, -STEP_Y, 0, 0),
+            WindowInput::Down => (0, STEP_Y, 0, 0),
+            WindowInput::Wider => (0, 0, STEP_X, 0),
+            WindowInput::Narrower => (0, 0, -STEP_X, 0),
+            WindowInput::Taller => (0, 0, 0, STEP_Y),
+            WindowInput::Shorter => (0, 0, 0, -STEP_Y),
+            WindowInput::Reset => return None,
+        };
+        let moved = Rect {
+            x: offset(area.x, dx),
+            y: offset(area.y, dy),
+            width: offset(area.width, dw).max(MIN_WIDTH),
+            height: offset(area.height, dh).max(MIN_HEIGHT),
+        };
+        Some(clamp(moved, bounds))
+    }
+}
+
+/// which corner of a window is being pulled
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragMode {
+    /// grabbed by the title bar
+    Move,
+    Resize(Corner),
+}
+
+/// a window held down by the mouse
+#[derive(Debug, Clone, Copy)]
+pub struct Drag {
+    pub kind: WidgetKind,
+    pub mode: DragMode,
+    /// where the window was when it was grabbed
+    start: Rect,
+    column: u16,
+    row: u16,
+}
+
+impl Drag {
+    /// grabs the window drawn at `area` if the press is on a corner or the title bar
+    pub fn grab(kind: WidgetKind, area: Rect, column: u16, row: u16) -> Option<Drag> {
+        let left = column == area.x;
+        let right = column + 1 == area.right();
+        let top = row == area.y;
+        let bottom = row + 1 == area.bottom();
+        let mode = match (left, right, top, bottom) {
+            (true, _, true, _) => DragMode::Resize(Corner::TopLeft),
+            (_, true, true, _) => DragMode::Resize(Corner::TopRight),
+            (true, _, _, true) => DragMode::Resize(Corner::BottomLeft),
+            (_, true, _, true) => DragMode::Resize(Corner::BottomRight),
+            (_, _, true, _) => DragMode::Move,
+            _ => return None,
+        };
+        Some(Drag { kind, mode, start: area, column, row })
+    }
+
+    /// where the window is with the mouse at `column`, `row`
+    pub fn area(&self, column: u16, row: u16, bounds: Rect) -> Rect {
+        let dx = column as i32 - self.column as i32;
+        let dy = row as i32 - self.row as i32;
+        let start = self.start;
+        if self.mode == DragMode::Move {
+            return clamp(Rect { x: offset(start.x, dx), y: offset(start.y, dy), ..start }, bounds);
+        }
+
+        //the opposite corner stays put
+        let (mut left, mut top) = (start.x as i32, start.y as i32);
+        let (mut right, mut bottom) = (start.right() as i32, start.bottom() as i32);
+        let (min_w, min_h) = (MIN_WIDTH as i32, MIN_HEIGHT as i32);
+        match self.mode {
+            DragMode::Resize(Corner::TopLeft | Corner::BottomLeft) => {
+                left = (left + dx).min(right - min_w).max(bounds.x as i32);
+            },
+            _ => right = (right + dx).max(left + min_w).min(bounds.right() as i32),
+        }
+        match self.mode {
+            DragMode::Resize(Corner::TopLeft | Corner::TopRight) => {
+                top = (top + dy).min(bottom - min_h).max(bounds.y as i32);
+            },
+            _ => bottom = (bottom + dy).max(top + min_h).min(bounds.bottom() as i32),
+        }
+        let resized = Rect {
+            x: left.max(0) as u16,
+            y: top.max(0) as u16,
+            width: (right - left).max(0) as u16,
+            height: (bottom - top).max(0) as u16,
+        };
+        clamp(resized, bounds)
+    }
+}
+
+/// keeps a window inside the terminal, shrinking it if the terminal got smaller
+pub fn clamp(area: Rect, bounds: Rect) -> Rect {
+    let width = area.width.min(bounds.width);
+    let height = area.height.min(bounds.height);
+    Rect {
+        x: area.x.clamp(bounds.x, bounds.right() - width),
+        y: area.y.clamp(bounds.y, bounds.bottom() - height),
+        width,
+        height,
+    }
+}
+
+fn offset(value: u16, by: i32) -> u16 {
+    (value as i32 + by).clamp(0, u16::MAX as i32) as u16
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SavedArea {
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+}
+
+/// puts the windows where they were left last session, a missing or broken file leaves them at their defaults
+pub fn load(widget_data: &mut WidgetData) {
+    let Some(path) = config_dir().map(|dir| dir.join(WINDOWS_FILE)) else {
+        return;
+    };
+    let saved: BTreeMap<String, SavedArea> = fs::read_to_string(path)
+        .ok()
+        .and_then(|text| toml::from_str(&text).ok())
+        .unwrap_or_default();
+    let kinds: Vec<WidgetKind> = widget_data.panes().map(|pane| pane.kind()).collect();
+    for kind in kinds {
+        if let Some(area) = saved.get(kind.name()) {
+            let area = Rect { x: area.x, y: area.y, width: area.width, height: area.height };
+            widget_data.set_area(kind, Some(area));
+        }
+    }
+}
+
+/// writes every moved window, the ones at their defaults are left out
+pub fn save(widget_data: &WidgetData) -> std::io::Result<()> {
+    let dir = config_dir().ok_or_else(|| std::io::Error::other("no config directory"))?;
+    fs::create_dir_all(&dir)?;
+    let saved: BTreeMap<String, SavedArea> = widget_data
+        .panes()
+        .filter_map(|pane| {
+            let kind = pane.kind();
+            let area = widget_data.get_state(kind).area()?;
+            Some((kind.name().to_string(), SavedArea { x: area.x, y: area.y, width: area.width, height: area.height }))
+        })
+        .collect();
+    let contents = toml::to_string(&saved).map_err(std::io::Error::other)?;
+    fs::write(dir.join(WINDOWS_FILE), contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: Rect = Rect { x: 0, y: 0, width: 80, height: 24 };
+
+    #[test]
+    fn a_window_inside_stays_put() {
+        let area = Rect { x: 10, y: 5, width: 30, height: 10 };
+        assert_eq!(clamp(area, BOUNDS), area);
+        let corner = Rect { x: 50, y: 14, width: 30, height: 10 };
+        assert_eq!(clamp(corner, BOUNDS), corner);
+    }
+
+    #[test]
+    fn a_window_past_the_edge_is_pushed_back_whole() {
+        let area = Rect { x: 70, y: 20, width: 30, height: 10 };
+        assert_eq!(clamp(area, BOUNDS), Rect { x: 50, y: 14, width: 30, height: 10 });
+    }
+
+    #[test]
+    fn a_window_bigger_than_the_terminal_shrinks_to_it() {
+        let area = Rect { x: 5, y: 3, width: 120, height: 40 };
+        assert_eq!(clamp(area, BOUNDS), BOUNDS);
+    }
+
+    #[test]
+    fn bounds_away_from_the_origin_are_respected() {
+        let bounds = Rect { x: 10, y: 4, width: 40, height: 12 };
+        let area = Rect { x: 0, y: 0, width: 20, height: 6 };
+        assert_eq!(clamp(area, bounds), Rect { x: 10, y: 4, width: 20, height: 6 });
+        let area = Rect { x: 45, y: 14, width: 20, height: 6 };
+        assert_eq!(clamp(area, bounds), Rect { x: 30, y: 10, width: 20, height: 6 });
+    }
+
+    #[test]
+    fn an_empty_terminal_leaves_nothing_to_draw() {
+        let bounds = Rect { x: 0, y: 0, width: 0, height: 0 };
+        let area = Rect { x: 3, y: 3, width: 20, height: 6 };
+        assert_eq!(clamp(area, bounds), bounds);
+    }
+}